@@ -0,0 +1,41 @@
+#!/bin/sh
+# compile the range circuits for every supported curve
+#   circom/bn128/range_{bits,interval}_<n>.{r1cs,wasm}
+#   circom/bls12-381/range_{bits,interval}_<n>.{r1cs,wasm}
+#   circom/<curve>/range_vector_64x<N>.{r1cs,wasm}
+#   circom/<curve>/range_context_64.{r1cs,wasm}
+# the .sym files name the committed signals for prover::prove_signals
+# the committed circuits were written by gen/ (same constraints and wire order as --O1, see gen/README.md)
+# no bls12-377 build : the legogroth16 circom loader only accepts the bn128 and bls12-381
+# scalar fields, bls12-377 uses circuit::RangeCircuit (setupNativeRangeBls12377)
+set -e
+
+cd "$(dirname "$0")"
+
+compile() {
+    name=$1
+    prime=$2
+    out=$3
+    circom "$name.circom" --O1 --r1cs --wasm --sym -p "$prime" -o "$out"
+    mv "$out/${name}_js/${name}.wasm" "$out/${name}.wasm"
+    rm -rf "$out/${name}_js"
+}
+
+build() {
+    prime=$1
+    out=../$2
+    mkdir -p "$out"
+    for kind in bits interval; do
+        for n in 8 16 32 64 128 252; do
+            compile "range_${kind}_${n}" "$prime" "$out"
+        done
+    done
+    for N in 2 4 8 16 32; do
+        compile "range_vector_64x${N}" "$prime" "$out"
+    done
+    compile "range_context_64" "$prime" "$out"
+}
+
+build bn128 bn128
+build bls12381 bls12-381
+echo "bls12-377 : not supported by the circom loader, use the native range circuit (setupNativeRangeBls12377)"
@@ -0,0 +1,384 @@
+// writes the circuits of circom/range/range.circom without circom, for every file of build.sh :
+//   <out>/<name>.r1cs : r1cs binary format v1 (header, constraints, wire to label map)
+//   <out>/<name>.sym  : label_index,wire_index,component_index,name (wire -1 for removed signals)
+//   <out>/<name>.wasm : witness calculator with the circom 2 wasm interface (witness.wat)
+// the constraints and wires are the ones of `circom --O1` : a signal assigned from a single signal
+// (bitsN.in <== value) is removed, every other constraint is kept
+//
+// usage : cargo run --release -- <circom dir>   (the directory holding bn128/ and bls12-381/)
+
+use num_bigint::{BigInt, BigUint, Sign};
+use std::{env, fs, path::Path};
+
+const BN128_PRIME : &str = "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+const BLS12_381_PRIME : &str = "52435875175126190479447740508185965837690552500527637822603658699938581184513";
+
+const RUNTIME : &str = include_str!("witness.wat");
+
+type Lc = Vec<(usize, BigInt)>;
+
+enum Op {
+    Sub(usize, usize, usize),
+    Bits(usize, usize, usize),
+    Mul(usize, usize, usize),
+}
+
+struct Label {
+    name : String,
+    component : usize,
+    // the label this one was assigned from (removed by --O1)
+    alias : Option<usize>,
+}
+
+struct Input {
+    name : String,
+    labels : Vec<usize>,
+    public : bool,
+}
+
+// signals are labels until wires are assigned (Circuit::wires)
+#[derive(Default)]
+struct Circuit {
+    labels : Vec<Label>,
+    components : usize,
+    inputs : Vec<Input>,
+    constraints : Vec<(Lc, Lc, Lc)>,
+    ops : Vec<Op>,
+}
+
+impl Circuit {
+    fn new() -> Self {
+        let mut circuit = Circuit::default();
+        circuit.labels.push(Label { name : "one".to_string(), component : 0, alias : None });
+        circuit.components = 1;
+        circuit
+    }
+
+    fn component(&mut self) -> usize {
+        self.components += 1;
+        self.components - 1
+    }
+
+    fn signal(&mut self, name : String, component : usize) -> usize {
+        self.labels.push(Label { name, component, alias : None });
+        self.labels.len() - 1
+    }
+
+    fn input(&mut self, name : &str, size : usize, public : bool) -> Vec<usize> {
+        let labels = if size == 1 {
+            vec![self.signal(format!("main.{}", name), 0)]
+        } else {
+            (0..size).map(|i| self.signal(format!("main.{}[{}]", name, i), 0)).collect()
+        };
+        self.inputs.push(Input { name : name.to_string(), labels : labels.clone(), public });
+        labels
+    }
+
+    fn resolve(&self, label : usize) -> usize {
+        match self.labels[label].alias {
+            Some(alias) => self.resolve(alias),
+            None => label,
+        }
+    }
+
+    // template RangeBits(n), in is assigned by the caller (assign, assign_difference)
+    fn range_bits(&mut self, prefix : &str, n : usize) -> usize {
+        let component = self.component();
+        let input = self.signal(format!("{}.in", prefix), component);
+        let bits = (0..n).map(|i| self.signal(format!("{}.bits[{}]", prefix, i), component)).collect::<Vec<_>>();
+
+        for bit in bits.iter() {
+            // bits[i] * (bits[i] - 1) === 0
+            self.constraints.push((
+                vec![(0, BigInt::from(-1)), (*bit, BigInt::from(1))],
+                vec![(*bit, BigInt::from(1))],
+                vec![]
+            ));
+        }
+        // bitsum === in
+        let mut bitsum = bits.iter().enumerate().map(|(i, bit)| (*bit, BigInt::from(1) << i)).collect::<Vec<_>>();
+        bitsum.push((input, BigInt::from(-1)));
+        self.constraints.push((vec![], vec![], bitsum));
+
+        self.ops.push(Op::Bits(bits[0], input, n));
+        input
+    }
+
+    // in <== signal
+    fn assign(&mut self, label : usize, from : usize) {
+        self.labels[label].alias = Some(from);
+    }
+
+    // in <== a - b
+    fn assign_difference(&mut self, label : usize, a : usize, b : usize) {
+        self.constraints.push((
+            vec![],
+            vec![],
+            vec![(a, BigInt::from(1)), (b, BigInt::from(-1)), (label, BigInt::from(-1))]
+        ));
+        self.ops.insert(0, Op::Sub(label, a, b));
+    }
+
+    // label -> wire : one, public inputs, private inputs, then the other kept signals in label order
+    fn wires(&self) -> Vec<Option<usize>> {
+        let mut order = vec![0];
+        for public in [true, false] {
+            for input in self.inputs.iter().filter(|input| input.public == public) {
+                order.extend(input.labels.iter().copied());
+            }
+        }
+        for label in 1..self.labels.len() {
+            if !order.contains(&label) && self.labels[label].alias.is_none() {
+                order.push(label);
+            }
+        }
+
+        let mut wires = vec![None; self.labels.len()];
+        for (wire, label) in order.iter().enumerate() {
+            wires[*label] = Some(wire);
+        }
+        wires
+    }
+}
+
+// component main = Main(n), 0 <= value < 2^n
+fn range_bits_circuit(n : usize) -> Circuit {
+    let mut circuit = Circuit::new();
+    let value = circuit.input("value", 1, false)[0];
+    let input = circuit.range_bits("main.bitsN", n);
+    circuit.assign(input, value);
+    circuit
+}
+
+// component main {public [min, max]} = RangeInterval(n)
+fn range_interval_circuit(n : usize) -> Circuit {
+    let mut circuit = Circuit::new();
+    let value = circuit.input("value", 1, false)[0];
+    let min = circuit.input("min", 1, true)[0];
+    let max = circuit.input("max", 1, true)[0];
+    let lower = circuit.range_bits("main.lower", n);
+    let upper = circuit.range_bits("main.upper", n);
+    circuit.assign_difference(lower, value, min);
+    circuit.assign_difference(upper, max, value);
+    circuit
+}
+
+// component main = RangeBitsVector(n, count)
+fn range_vector_circuit(n : usize, count : usize) -> Circuit {
+    let mut circuit = Circuit::new();
+    let values = circuit.input("values", count, false);
+    for (i, value) in values.iter().enumerate() {
+        let input = circuit.range_bits(&format!("main.bitsN[{}]", i), n);
+        circuit.assign(input, *value);
+    }
+    circuit
+}
+
+// component main {public [context]} = Main(n) with RangeBits and Context
+fn range_context_circuit(n : usize) -> Circuit {
+    let mut circuit = Circuit::new();
+    let value = circuit.input("value", 1, false)[0];
+    let context = circuit.input("context", 1, true)[0];
+    let input = circuit.range_bits("main.bitsN", n);
+    circuit.assign(input, value);
+
+    let component = circuit.component();
+    let ctx_context = circuit.signal("main.ctx.context".to_string(), component);
+    let context_squared = circuit.signal("main.ctx.context_squared".to_string(), component);
+    circuit.assign(ctx_context, context);
+    // context_squared <== context * context
+    circuit.constraints.push((
+        vec![(context, BigInt::from(1))],
+        vec![(context, BigInt::from(1))],
+        vec![(context_squared, BigInt::from(1))]
+    ));
+    circuit.ops.push(Op::Mul(context_squared, context, context));
+    circuit
+}
+
+fn field_bytes(value : &BigInt, prime : &BigUint) -> Vec<u8> {
+    let prime = BigInt::from_biguint(Sign::Plus, prime.clone());
+    let (_, mut bytes) = (((value % &prime) + &prime) % &prime).to_bytes_le();
+    bytes.resize(32, 0);
+    bytes
+}
+
+fn prime_bytes(prime : &BigUint) -> Vec<u8> {
+    let mut bytes = prime.to_bytes_le();
+    bytes.resize(32, 0);
+    bytes
+}
+
+fn u32_bytes(value : usize) -> [u8; 4] {
+    (value as u32).to_le_bytes()
+}
+
+fn r1cs(circuit : &Circuit, prime : &BigUint) -> Vec<u8> {
+    let wires = circuit.wires();
+    let n_wires = wires.iter().filter(|wire| wire.is_some()).count();
+    let n_pub_in = circuit.inputs.iter().filter(|input| input.public).map(|input| input.labels.len()).sum::<usize>();
+    let n_prv_in = circuit.inputs.iter().filter(|input| !input.public).map(|input| input.labels.len()).sum::<usize>();
+
+    let mut header = u32_bytes(32).to_vec();
+    header.extend(prime_bytes(prime));
+    header.extend(u32_bytes(n_wires));
+    header.extend(u32_bytes(0));
+    header.extend(u32_bytes(n_pub_in));
+    header.extend(u32_bytes(n_prv_in));
+    header.extend((circuit.labels.len() as u64).to_le_bytes());
+    header.extend(u32_bytes(circuit.constraints.len()));
+
+    let mut constraints = Vec::new();
+    for (a, b, c) in circuit.constraints.iter() {
+        for lc in [a, b, c] {
+            // terms of the same wire are merged, sorted by wire
+            let mut terms : Vec<(usize, BigInt)> = Vec::new();
+            for (label, coefficient) in lc {
+                let wire = wires[circuit.resolve(*label)].unwrap();
+                match terms.iter_mut().find(|(w, _)| *w == wire) {
+                    Some((_, c)) => *c += coefficient,
+                    None => terms.push((wire, coefficient.clone())),
+                }
+            }
+            terms.sort_by_key(|(wire, _)| *wire);
+            constraints.extend(u32_bytes(terms.len()));
+            for (wire, coefficient) in terms {
+                constraints.extend(u32_bytes(wire));
+                constraints.extend(field_bytes(&coefficient, prime));
+            }
+        }
+    }
+
+    let mut wire_to_label = vec![0u64; n_wires];
+    for (label, wire) in wires.iter().enumerate() {
+        if let Some(wire) = wire {
+            wire_to_label[*wire] = label as u64;
+        }
+    }
+    let map = wire_to_label.iter().flat_map(|label| label.to_le_bytes()).collect::<Vec<_>>();
+
+    let mut file = b"r1cs".to_vec();
+    file.extend(u32_bytes(1));
+    file.extend(u32_bytes(3));
+    for (section, bytes) in [(1, header), (2, constraints), (3, map)] {
+        file.extend(u32_bytes(section));
+        file.extend((bytes.len() as u64).to_le_bytes());
+        file.extend(bytes);
+    }
+    file
+}
+
+fn sym(circuit : &Circuit) -> String {
+    let wires = circuit.wires();
+    circuit.labels
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, label)| {
+            let wire = wires[i].map_or(-1, |wire| wire as i64);
+            format!("{},{},{},{}\n", i, wire, label.component, label.name)
+        })
+        .collect()
+}
+
+// signal name hash of the circom witness calculator (64 bit FNV-1a)
+fn fnv(name : &str) -> u64 {
+    name.bytes().fold(0xcbf29ce484222325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
+
+fn data(bytes : &[u8]) -> String {
+    bytes.iter().map(|byte| format!("\\{:02x}", byte)).collect()
+}
+
+fn wasm(circuit : &Circuit, prime : &BigUint) -> Vec<u8> {
+    let wires = circuit.wires();
+    let wire = |label : usize| wires[circuit.resolve(label)].unwrap();
+    let n_wires = wires.iter().filter(|wire| wire.is_some()).count();
+    let input_count = circuit.inputs.iter().map(|input| input.labels.len()).sum::<usize>();
+
+    let mut inputs = Vec::new();
+    for input in circuit.inputs.iter() {
+        let hash = fnv(&input.name);
+        inputs.extend(u32_bytes((hash >> 32) as usize));
+        inputs.extend(u32_bytes(hash as u32 as usize));
+        inputs.extend(u32_bytes(input.labels.len()));
+        inputs.extend(u32_bytes(wire(input.labels[0])));
+    }
+    let mut program = Vec::new();
+    for op in circuit.ops.iter() {
+        let (code, a, b, c) = match op {
+            Op::Sub(d, a, b) => (1, wire(*d), wire(*a), wire(*b)),
+            Op::Bits(d, s, n) => (2, wire(*d), wire(*s), *n),
+            Op::Mul(d, a, b) => (3, wire(*d), wire(*a), wire(*b)),
+        };
+        for value in [code, a, b, c] {
+            program.extend(u32_bytes(value));
+        }
+    }
+
+    let inputs_start = 256;
+    let program_start = inputs_start + inputs.len();
+    let set_start = program_start + program.len();
+    let witness_start = (set_start + n_wires + 31) / 32 * 32;
+    let end = witness_start + 32 * n_wires;
+    let pages = end.div_ceil(65536);
+
+    let module = format!(
+        r#"(module
+  (import "runtime" "exceptionHandler" (func $exceptionHandler (param i32)))
+  (import "runtime" "printErrorMessage" (func $printErrorMessage))
+  (import "runtime" "writeBufferMessage" (func $writeBufferMessage))
+  (import "runtime" "showSharedRWMemory" (func $showSharedRWMemory))
+  (memory (export "memory") {pages})
+
+  (global $SHARED i32 (i32.const 0))
+  (global $PRIME i32 (i32.const 64))
+  (global $TMP i32 (i32.const 128))
+  (global $INPUTS i32 (i32.const {inputs_start}))
+  (global $N_INPUTS i32 (i32.const {n_inputs}))
+  (global $INPUT_COUNT i32 (i32.const {input_count}))
+  (global $PROGRAM i32 (i32.const {program_start}))
+  (global $N_OPS i32 (i32.const {n_ops}))
+  (global $SET i32 (i32.const {set_start}))
+  (global $WITNESS i32 (i32.const {witness_start}))
+  (global $N_WIRES i32 (i32.const {n_wires}))
+  (global $remaining (mut i32) (i32.const 0))
+
+  (data (i32.const 64) "{prime}")
+  (data (i32.const {inputs_start}) "{inputs}")
+  (data (i32.const {program_start}) "{program}")
+
+{runtime})
+"#,
+        n_inputs = circuit.inputs.len(),
+        n_ops = circuit.ops.len(),
+        prime = data(&prime_bytes(prime)),
+        inputs = data(&inputs),
+        program = data(&program),
+        runtime = RUNTIME,
+    );
+    wat::parse_str(module).expect("invalid witness calculator")
+}
+
+fn write_circuit(out : &Path, name : &str, circuit : &Circuit, prime : &BigUint) {
+    fs::write(out.join(format!("{}.r1cs", name)), r1cs(circuit, prime)).unwrap();
+    fs::write(out.join(format!("{}.sym", name)), sym(circuit)).unwrap();
+    fs::write(out.join(format!("{}.wasm", name)), wasm(circuit, prime)).unwrap();
+}
+
+fn main() {
+    let circom_dir = env::args().nth(1).unwrap_or_else(|| "../..".to_string());
+    for (dir, prime) in [("bn128", BN128_PRIME), ("bls12-381", BLS12_381_PRIME)] {
+        let prime = prime.parse::<BigUint>().unwrap();
+        let out = Path::new(&circom_dir).join(dir);
+        fs::create_dir_all(&out).unwrap();
+        for n in [8, 16, 32, 64, 128, 252] {
+            write_circuit(&out, &format!("range_bits_{}", n), &range_bits_circuit(n), &prime);
+            write_circuit(&out, &format!("range_interval_{}", n), &range_interval_circuit(n), &prime);
+        }
+        for count in [2, 4, 8, 16, 32] {
+            write_circuit(&out, &format!("range_vector_64x{}", count), &range_vector_circuit(64, count), &prime);
+        }
+        write_circuit(&out, "range_context_64", &range_context_circuit(64), &prime);
+    }
+}
@@ -0,0 +1,215 @@
+  ;; witness calculator runtime, same exports and imports as a circom 2 wasm
+  ;; (circom_runtime witness_calculator.js and legogroth16::circom::WitnessCalculator)
+  ;;
+  ;; the circuit is a list of 16 byte ops (opcode, a, b, c) over wires, run once every input is set
+  ;;   1 : wire a = wire b - wire c
+  ;;   2 : wires a .. a+c-1 = bits of wire b, which must be less than 2^c (RangeBits)
+  ;;   3 : wire a = wire b * wire c
+  ;; input table : 16 byte entries (fnv hash msb, lsb, size, first wire)
+  ;;
+  ;; exception codes
+  ;;   1 : unknown input signal
+  ;;   2 : too many values for the input signal
+  ;;   3 : input value set twice
+  ;;   4 : assert failed (a value is out of range)
+  ;;   5 : witness index out of range
+
+  (func $fail (param $code i32)
+    (call $exceptionHandler (local.get $code))
+    unreachable)
+
+  (func $wire (param $w i32) (result i32)
+    (i32.add (global.get $WITNESS) (i32.shl (local.get $w) (i32.const 5))))
+
+  (func $copy (param $d i32) (param $s i32)
+    (local $i i32)
+    (loop $l
+      (i32.store (i32.add (local.get $d) (local.get $i)) (i32.load (i32.add (local.get $s) (local.get $i))))
+      (local.set $i (i32.add (local.get $i) (i32.const 4)))
+      (br_if $l (i32.lt_u (local.get $i) (i32.const 32)))))
+
+  (func $zero (param $d i32) (param $len i32)
+    (local $i i32)
+    (block $end
+      (loop $l
+        (br_if $end (i32.ge_u (local.get $i) (local.get $len)))
+        (i32.store8 (i32.add (local.get $d) (local.get $i)) (i32.const 0))
+        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+        (br $l))))
+
+  ;; 1 if a >= b
+  (func $gte (param $a i32) (param $b i32) (result i32)
+    (local $i i32) (local $x i32) (local $y i32)
+    (local.set $i (i32.const 28))
+    (loop $l
+      (local.set $x (i32.load (i32.add (local.get $a) (local.get $i))))
+      (local.set $y (i32.load (i32.add (local.get $b) (local.get $i))))
+      (if (i32.gt_u (local.get $x) (local.get $y)) (then (return (i32.const 1))))
+      (if (i32.lt_u (local.get $x) (local.get $y)) (then (return (i32.const 0))))
+      (if (i32.eqz (local.get $i)) (then (return (i32.const 1))))
+      (local.set $i (i32.sub (local.get $i) (i32.const 4)))
+      (br $l))
+    (i32.const 1))
+
+  ;; d = a + b mod 2^256, returns the carry
+  (func $add_raw (param $d i32) (param $a i32) (param $b i32) (result i32)
+    (local $i i32) (local $r i64)
+    (loop $l
+      (local.set $r
+        (i64.add
+          (i64.add
+            (i64.load32_u (i32.add (local.get $a) (local.get $i)))
+            (i64.load32_u (i32.add (local.get $b) (local.get $i))))
+          (i64.shr_u (local.get $r) (i64.const 32))))
+      (i32.store (i32.add (local.get $d) (local.get $i)) (i32.wrap_i64 (local.get $r)))
+      (local.set $i (i32.add (local.get $i) (i32.const 4)))
+      (br_if $l (i32.lt_u (local.get $i) (i32.const 32))))
+    (i32.wrap_i64 (i64.shr_u (local.get $r) (i64.const 32))))
+
+  ;; d = a - b mod 2^256, returns the borrow
+  (func $sub_raw (param $d i32) (param $a i32) (param $b i32) (result i32)
+    (local $i i32) (local $r i64) (local $borrow i64)
+    (loop $l
+      (local.set $r
+        (i64.sub
+          (i64.sub
+            (i64.load32_u (i32.add (local.get $a) (local.get $i)))
+            (i64.load32_u (i32.add (local.get $b) (local.get $i))))
+          (local.get $borrow)))
+      (i32.store (i32.add (local.get $d) (local.get $i)) (i32.wrap_i64 (local.get $r)))
+      (local.set $borrow (i64.shr_u (local.get $r) (i64.const 63)))
+      (local.set $i (i32.add (local.get $i) (i32.const 4)))
+      (br_if $l (i32.lt_u (local.get $i) (i32.const 32))))
+    (i32.wrap_i64 (local.get $borrow)))
+
+  ;; a, b < p
+  (func $add_mod (param $d i32) (param $a i32) (param $b i32)
+    (if (i32.or
+          (call $add_raw (local.get $d) (local.get $a) (local.get $b))
+          (call $gte (local.get $d) (global.get $PRIME)))
+      (then (drop (call $sub_raw (local.get $d) (local.get $d) (global.get $PRIME))))))
+
+  (func $sub_mod (param $d i32) (param $a i32) (param $b i32)
+    (if (call $sub_raw (local.get $d) (local.get $a) (local.get $b))
+      (then (drop (call $add_raw (local.get $d) (local.get $d) (global.get $PRIME))))))
+
+  (func $bit (param $a i32) (param $i i32) (result i32)
+    (i32.and
+      (i32.shr_u
+        (i32.load (i32.add (local.get $a) (i32.shl (i32.shr_u (local.get $i) (i32.const 5)) (i32.const 2))))
+        (i32.and (local.get $i) (i32.const 31)))
+      (i32.const 1)))
+
+  ;; double and add, the circuits multiply once per witness
+  (func $mul_mod (param $d i32) (param $a i32) (param $b i32)
+    (local $i i32)
+    (call $zero (global.get $TMP) (i32.const 32))
+    (local.set $i (i32.const 256))
+    (loop $l
+      (local.set $i (i32.sub (local.get $i) (i32.const 1)))
+      (call $add_mod (global.get $TMP) (global.get $TMP) (global.get $TMP))
+      (if (call $bit (local.get $b) (local.get $i))
+        (then (call $add_mod (global.get $TMP) (global.get $TMP) (local.get $a))))
+      (br_if $l (local.get $i)))
+    (call $copy (local.get $d) (global.get $TMP)))
+
+  (func $bits (param $d i32) (param $s i32) (param $n i32)
+    (local $i i32) (local $src i32) (local $dst i32)
+    (local.set $src (call $wire (local.get $s)))
+    (loop $l
+      (if (i32.lt_u (local.get $i) (local.get $n))
+        (then
+          (local.set $dst (call $wire (i32.add (local.get $d) (local.get $i))))
+          (call $zero (local.get $dst) (i32.const 32))
+          (i32.store (local.get $dst) (call $bit (local.get $src) (local.get $i))))
+        (else
+          (if (call $bit (local.get $src) (local.get $i)) (then (call $fail (i32.const 4))))))
+      (local.set $i (i32.add (local.get $i) (i32.const 1)))
+      (br_if $l (i32.lt_u (local.get $i) (i32.const 256)))))
+
+  (func $run
+    (local $k i32) (local $op i32) (local $code i32) (local $a i32) (local $b i32) (local $c i32)
+    (block $end
+      (loop $l
+        (br_if $end (i32.ge_u (local.get $k) (global.get $N_OPS)))
+        (local.set $op (i32.add (global.get $PROGRAM) (i32.shl (local.get $k) (i32.const 4))))
+        (local.set $code (i32.load (local.get $op)))
+        (local.set $a (i32.load offset=4 (local.get $op)))
+        (local.set $b (i32.load offset=8 (local.get $op)))
+        (local.set $c (i32.load offset=12 (local.get $op)))
+        (if (i32.eq (local.get $code) (i32.const 1))
+          (then (call $sub_mod (call $wire (local.get $a)) (call $wire (local.get $b)) (call $wire (local.get $c)))))
+        (if (i32.eq (local.get $code) (i32.const 2))
+          (then (call $bits (local.get $a) (local.get $b) (local.get $c))))
+        (if (i32.eq (local.get $code) (i32.const 3))
+          (then (call $mul_mod (call $wire (local.get $a)) (call $wire (local.get $b)) (call $wire (local.get $c)))))
+        (local.set $k (i32.add (local.get $k) (i32.const 1)))
+        (br $l))))
+
+  ;; input table entry of the signal, 0 if there is none
+  (func $find_input (param $hmsb i32) (param $hlsb i32) (result i32)
+    (local $k i32) (local $e i32)
+    (block $end
+      (loop $l
+        (br_if $end (i32.ge_u (local.get $k) (global.get $N_INPUTS)))
+        (local.set $e (i32.add (global.get $INPUTS) (i32.shl (local.get $k) (i32.const 4))))
+        (if (i32.and
+              (i32.eq (i32.load (local.get $e)) (local.get $hmsb))
+              (i32.eq (i32.load offset=4 (local.get $e)) (local.get $hlsb)))
+          (then (return (local.get $e))))
+        (local.set $k (i32.add (local.get $k) (i32.const 1)))
+        (br $l)))
+    (i32.const 0))
+
+  (func (export "getVersion") (result i32) (i32.const 2))
+  (func (export "getMinorVersion") (result i32) (i32.const 1))
+  (func (export "getPatchVersion") (result i32) (i32.const 0))
+  (func (export "getSharedRWMemoryStart") (result i32) (global.get $SHARED))
+  (func (export "getFieldNumLen32") (result i32) (i32.const 8))
+  (func (export "getInputSize") (result i32) (global.get $INPUT_COUNT))
+  (func (export "getWitnessSize") (result i32) (global.get $N_WIRES))
+  (func (export "getMessageChar") (result i32) (i32.const 0))
+
+  (func (export "readSharedRWMemory") (param $i i32) (result i32)
+    (i32.load (i32.add (global.get $SHARED) (i32.shl (local.get $i) (i32.const 2)))))
+
+  (func (export "writeSharedRWMemory") (param $i i32) (param $v i32)
+    (i32.store (i32.add (global.get $SHARED) (i32.shl (local.get $i) (i32.const 2))) (local.get $v)))
+
+  (func (export "getRawPrime")
+    (call $copy (global.get $SHARED) (global.get $PRIME)))
+
+  (func (export "init") (param $sanity_check i32)
+    (global.set $remaining (global.get $INPUT_COUNT))
+    (call $zero (global.get $SET) (global.get $N_WIRES))
+    (call $zero (global.get $WITNESS) (i32.shl (global.get $N_WIRES) (i32.const 5)))
+    (i32.store (global.get $WITNESS) (i32.const 1)))
+
+  (func (export "getInputSignalSize") (param $hmsb i32) (param $hlsb i32) (result i32)
+    (local $e i32)
+    (local.set $e (call $find_input (local.get $hmsb) (local.get $hlsb)))
+    (if (result i32) (local.get $e)
+      (then (i32.load offset=8 (local.get $e)))
+      (else (i32.const -1))))
+
+  (func (export "setInputSignal") (param $hmsb i32) (param $hlsb i32) (param $pos i32)
+    (local $e i32) (local $w i32)
+    (local.set $e (call $find_input (local.get $hmsb) (local.get $hlsb)))
+    (if (i32.eqz (local.get $e)) (then (call $fail (i32.const 1))))
+    (if (i32.ge_u (local.get $pos) (i32.load offset=8 (local.get $e))) (then (call $fail (i32.const 2))))
+    (local.set $w (i32.add (i32.load offset=12 (local.get $e)) (local.get $pos)))
+    (if (i32.load8_u (i32.add (global.get $SET) (local.get $w))) (then (call $fail (i32.const 3))))
+    (i32.store8 (i32.add (global.get $SET) (local.get $w)) (i32.const 1))
+    ;; values are taken mod p
+    (block $reduced
+      (loop $l
+        (br_if $reduced (i32.eqz (call $gte (global.get $SHARED) (global.get $PRIME))))
+        (drop (call $sub_raw (global.get $SHARED) (global.get $SHARED) (global.get $PRIME)))
+        (br $l)))
+    (call $copy (call $wire (local.get $w)) (global.get $SHARED))
+    (global.set $remaining (i32.sub (global.get $remaining) (i32.const 1)))
+    (if (i32.eqz (global.get $remaining)) (then (call $run))))
+
+  (func (export "getWitness") (param $i i32)
+    (if (i32.ge_u (local.get $i) (global.get $N_WIRES)) (then (call $fail (i32.const 5))))
+    (call $copy (global.get $SHARED) (call $wire (local.get $i))))
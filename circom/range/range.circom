@@ -0,0 +1,50 @@
+pragma circom 2.0.0;
+
+// to check  0 <= in < 2^n
+template RangeBits(n){
+    signal input in;
+    signal bits[n];
+    var bitsum = 0;
+    for (var i = 0; i < n; i++) {
+        bits[i] <-- (in >> i) & 1;          // i-th bit of in
+        bits[i] * (bits[i] - 1) === 0;      // bits[i] binary check
+        bitsum = bitsum + 2 ** i * bits[i];
+    }
+    bitsum === in;
+}
+
+// to check  min <= value <= max
+// n must satisfy  max - min < 2^n  and  2^(n+1) < field order
+template RangeInterval(n) {
+    signal input value;
+    signal input min;
+    signal input max;
+
+    component lower = RangeBits(n);
+    component upper = RangeBits(n);
+
+    lower.in <== value - min;
+    upper.in <== max - value;
+}
+
+// to check  0 <= values[i] < 2^n  for i in [0, N)
+// values are the first N private signals, so all of them are committed in proof.d
+template RangeBitsVector(n, N) {
+    signal input values[N];
+
+    component bitsN[N];
+    for (var i = 0; i < N; i++) {
+        bitsN[i] = RangeBits(n);
+        bitsN[i].in <== values[i];
+    }
+}
+
+// binds the proof to an application context (session id, account id, epoch, ...)
+// context is a public input holding the hash of the context bytes (context::context_to_field)
+// the square keeps the otherwise unused input in the constraints
+template Context() {
+    signal input context;
+    signal context_squared;
+
+    context_squared <== context * context;
+}
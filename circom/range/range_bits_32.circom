@@ -0,0 +1,14 @@
+pragma circom 2.0.0;
+
+include "range.circom";
+
+// 0 <= value < 2^32
+template Main(n) {
+    signal input value;
+
+    component bitsN = RangeBits(n);
+
+    bitsN.in <== value;
+}
+
+component main = Main(32);
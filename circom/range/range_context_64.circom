@@ -0,0 +1,19 @@
+pragma circom 2.0.0;
+
+include "range.circom";
+
+// 0 <= value < 2^64, bound to a context
+// value is the first private signal, so it is committed in proof.d
+// context must be the last public input (verifier::verify_with_context)
+template Main(n) {
+    signal input value;
+    signal input context;
+
+    component bitsN = RangeBits(n);
+    component ctx = Context();
+
+    bitsN.in <== value;
+    ctx.context <== context;
+}
+
+component main {public [context]} = Main(64);
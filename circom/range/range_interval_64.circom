@@ -0,0 +1,8 @@
+pragma circom 2.0.0;
+
+include "range.circom";
+
+// min <= value <= max, max - min < 2^64
+// public  : min, max
+// private : value (committed witness)
+component main {public [min, max]} = RangeInterval(64);
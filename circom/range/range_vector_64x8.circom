@@ -0,0 +1,7 @@
+pragma circom 2.0.0;
+
+include "range.circom";
+
+// 0 <= values[i] < 2^64 for 8 values
+// setup with commit_witness_count = 8
+component main = RangeBitsVector(64, 8);
@@ -17,6 +17,16 @@ export function setupFromCircomR1CsBls12381(
   pathPk: string,
   pathVk: string,
 ): void
+export interface KeyBuffers {
+  pk: Buffer
+  vk: Buffer
+}
+export interface ProofBuffer {
+  proof: Buffer
+  openingKey: string
+}
+export function setupFromCircomR1CsBufferBn128(r1Cs: Buffer, commitWitnessCount: number, seed: number): KeyBuffers
+export function setupFromCircomR1CsBufferBls12381(r1Cs: Buffer, commitWitnessCount: number, seed: number): KeyBuffers
 export function proveRangeBn128(
   r1CsFilePath: string,
   wasmFilePath: string,
@@ -24,6 +34,7 @@ export function proveRangeBn128(
   proofFilePath: string,
   inputString: string,
   seed: number,
+  reduce?: boolean | undefined | null,
 ): void
 export function proveRangeBls12381(
   r1CsFilePath: string,
@@ -32,16 +43,23 @@ export function proveRangeBls12381(
   proofFilePath: string,
   inputString: string,
   seed: number,
+  reduce?: boolean | undefined | null,
 ): void
 export function verifyRangeBn128(vkPath: string, proofFilePath: string): boolean
 export function verifyRangeBls12381(vkPath: string, proofFilePath: string): boolean
+// the *Bls12377 exports take keys and proofs of native circuits only (setupNativeRangeBls12377,
+// proveNativeRangeBls12377), circom r1cs / wasm files are bn128 or bls12-381
+export function verifyRangeBls12377(vkPath: string, proofFilePath: string): boolean
 export function getProofBn128(proofFilePath: string): string
 export function aggregateProofCommitmentBn128(proofFilePaths: Array<string>, saveFilePath: string): void
 export function aggregateProofCommitmentBls12381(proofFilePaths: Array<string>, saveFilePath: string): void
+export function aggregateProofCommitmentBls12377(proofFilePaths: Array<string>, saveFilePath: string): void
 export function getAggregatedCommitmentBn128(aggregatedCommitmentFilePath: string): string
 export function getAggregatedCommitmentBls12381(aggregatedCommitmentFilePath: string): string
+export function getAggregatedCommitmentBls12377(aggregatedCommitmentFilePath: string): string
 export function aggregateOpeningKeysBn128(openingKeyFilePaths: Array<string>, saveFilePath: string): void
 export function aggregateOpeningKeysBls12381(openingKeyFilePaths: Array<string>, saveFilePath: string): void
+export function aggregateOpeningKeysBls12377(openingKeyFilePaths: Array<string>, saveFilePath: string): void
 export function updateAggregatedCommitmentBn128(
   r1CsFilePath: string,
   keyFilePath: string,
@@ -49,8 +67,659 @@ export function updateAggregatedCommitmentBn128(
   proofFilePath: string,
   aggregatedCommitmentFilePath: string,
   aggregatedOpeningKeyFilePath: string,
-  updateValue: string,
+  updateValues: string | Array<string>,
   seed: number,
+  reduce?: boolean | undefined | null,
 ): void
-export function updateAggregatedCommitmentBls12381(): void
+export function updateAggregatedCommitmentBls12381(
+  r1CsFilePath: string,
+  keyFilePath: string,
+  wasmFilePath: string,
+  proofFilePath: string,
+  aggregatedCommitmentFilePath: string,
+  aggregatedOpeningKeyFilePath: string,
+  updateValues: string | Array<string>,
+  seed: number,
+  reduce?: boolean | undefined | null,
+): void
+export function removeFromAggregateBn128(
+  vkFilePath: string,
+  proofFilePath: string,
+  openingKeyFilePath: string | undefined | null,
+  aggregatedCommitmentFilePath: string,
+  aggregatedOpeningKeyFilePath: string,
+  publicInputs?: Array<string> | undefined | null,
+): void
+export function removeFromAggregateBls12381(
+  vkFilePath: string,
+  proofFilePath: string,
+  openingKeyFilePath: string | undefined | null,
+  aggregatedCommitmentFilePath: string,
+  aggregatedOpeningKeyFilePath: string,
+  publicInputs?: Array<string> | undefined | null,
+): void
+export function removeFromAggregateBls12377(
+  vkFilePath: string,
+  proofFilePath: string,
+  openingKeyFilePath: string | undefined | null,
+  aggregatedCommitmentFilePath: string,
+  aggregatedOpeningKeyFilePath: string,
+  publicInputs?: Array<string> | undefined | null,
+): void
+export function proveAggregatedTotalBn128(vkFilePath: string, aggregatedOpeningKeyFilePath: string): string
+export function proveAggregatedTotalBls12381(vkFilePath: string, aggregatedOpeningKeyFilePath: string): string
+export function proveAggregatedTotalBls12377(vkFilePath: string, aggregatedOpeningKeyFilePath: string): string
+export function verifyAggregatedTotalBn128(
+  aggregatedCommitmentFilePath: string,
+  vkFilePath: string,
+  total: string,
+  blinding?: string | undefined | null,
+  blindingProof?: string | undefined | null,
+): boolean
+export function verifyAggregatedTotalBls12381(
+  aggregatedCommitmentFilePath: string,
+  vkFilePath: string,
+  total: string,
+  blinding?: string | undefined | null,
+  blindingProof?: string | undefined | null,
+): boolean
+export function verifyAggregatedTotalBls12377(
+  aggregatedCommitmentFilePath: string,
+  vkFilePath: string,
+  total: string,
+  blinding?: string | undefined | null,
+  blindingProof?: string | undefined | null,
+): boolean
+export function proveAggregateRangeBn128(
+  r1csFilePath: string,
+  wasmFilePath: string,
+  pkFilePath: string,
+  aggregatedOpeningKeyFilePath: string,
+  proofFilePath: string,
+  bitWidth: number,
+  limit: string | undefined | null,
+  seed: number,
+): void
+export function proveAggregateRangeBls12381(
+  r1csFilePath: string,
+  wasmFilePath: string,
+  pkFilePath: string,
+  aggregatedOpeningKeyFilePath: string,
+  proofFilePath: string,
+  bitWidth: number,
+  limit: string | undefined | null,
+  seed: number,
+): void
+export function verifyAggregateRangeBn128(
+  vkFilePath: string,
+  proofFilePath: string,
+  aggregatedCommitmentFilePath: string,
+  bitWidth: number,
+  limit?: string | undefined | null,
+  publicInputs?: Array<string> | undefined | null,
+): boolean
+export function verifyAggregateRangeBls12381(
+  vkFilePath: string,
+  proofFilePath: string,
+  aggregatedCommitmentFilePath: string,
+  bitWidth: number,
+  limit?: string | undefined | null,
+  publicInputs?: Array<string> | undefined | null,
+): boolean
+export function verifyAggregateRangeBls12377(
+  vkFilePath: string,
+  proofFilePath: string,
+  aggregatedCommitmentFilePath: string,
+  bitWidth: number,
+  limit?: string | undefined | null,
+  publicInputs?: Array<string> | undefined | null,
+): boolean
+export function proveCommitmentEqualityBn128(
+  vkFilePath: string,
+  openingKeyFilePath: string,
+  externalBases: Array<string>,
+  externalBlindingBase: string,
+  externalBlinding: string,
+): string
+export function proveCommitmentEqualityBls12381(
+  vkFilePath: string,
+  openingKeyFilePath: string,
+  externalBases: Array<string>,
+  externalBlindingBase: string,
+  externalBlinding: string,
+): string
+export function proveCommitmentEqualityBls12377(
+  vkFilePath: string,
+  openingKeyFilePath: string,
+  externalBases: Array<string>,
+  externalBlindingBase: string,
+  externalBlinding: string,
+): string
+export function verifyCommitmentEqualityBn128(
+  vkFilePath: string,
+  proofFilePath: string,
+  externalCommitment: string,
+  externalBases: Array<string>,
+  externalBlindingBase: string,
+  equalityProof: string,
+): boolean
+export function verifyCommitmentEqualityBls12381(
+  vkFilePath: string,
+  proofFilePath: string,
+  externalCommitment: string,
+  externalBases: Array<string>,
+  externalBlindingBase: string,
+  equalityProof: string,
+): boolean
+export function verifyCommitmentEqualityBls12377(
+  vkFilePath: string,
+  proofFilePath: string,
+  externalCommitment: string,
+  externalBases: Array<string>,
+  externalBlindingBase: string,
+  equalityProof: string,
+): boolean
+export function setupInsecureAggregationSrsBn128(maxProofs: number, seed: number, srsFilePath: string): void
+export function setupInsecureAggregationSrsBls12381(maxProofs: number, seed: number, srsFilePath: string): void
+export function setupInsecureAggregationSrsBls12377(maxProofs: number, seed: number, srsFilePath: string): void
+export function aggregateProofsBn128(
+  srsFilePath: string,
+  proofFilePaths: Array<string>,
+  aggregatedProofFilePath: string,
+  publicInputs?: Array<Array<string>> | undefined | null,
+): void
+export function aggregateProofsBls12381(
+  srsFilePath: string,
+  proofFilePaths: Array<string>,
+  aggregatedProofFilePath: string,
+  publicInputs?: Array<Array<string>> | undefined | null,
+): void
+export function aggregateProofsBls12377(
+  srsFilePath: string,
+  proofFilePaths: Array<string>,
+  aggregatedProofFilePath: string,
+  publicInputs?: Array<Array<string>> | undefined | null,
+): void
+export function verifyAggregatedProofsBn128(
+  srsFilePath: string,
+  vkFilePath: string,
+  aggregatedProofFilePath: string,
+  aggregatedCommitmentFilePath: string,
+  publicInputs?: Array<Array<string>> | undefined | null,
+): boolean
+export function verifyAggregatedProofsBls12381(
+  srsFilePath: string,
+  vkFilePath: string,
+  aggregatedProofFilePath: string,
+  aggregatedCommitmentFilePath: string,
+  publicInputs?: Array<Array<string>> | undefined | null,
+): boolean
+export function verifyAggregatedProofsBls12377(
+  srsFilePath: string,
+  vkFilePath: string,
+  aggregatedProofFilePath: string,
+  aggregatedCommitmentFilePath: string,
+  publicInputs?: Array<Array<string>> | undefined | null,
+): boolean
+export function rerandomizeProofBn128(
+  vkFilePath: string,
+  proofFilePath: string,
+  rerandomizedProofFilePath: string,
+  pkFilePath?: string | undefined | null,
+  openingKeyFilePath?: string | undefined | null,
+): string | null
+export function rerandomizeProofBls12381(
+  vkFilePath: string,
+  proofFilePath: string,
+  rerandomizedProofFilePath: string,
+  pkFilePath?: string | undefined | null,
+  openingKeyFilePath?: string | undefined | null,
+): string | null
+export function rerandomizeProofBls12377(
+  vkFilePath: string,
+  proofFilePath: string,
+  rerandomizedProofFilePath: string,
+  pkFilePath?: string | undefined | null,
+  openingKeyFilePath?: string | undefined | null,
+): string | null
+export function contextToFieldBn128(context: Buffer): string
+export function contextToFieldBls12381(context: Buffer): string
+export function contextToFieldBls12377(context: Buffer): string
+export function proveRangeWithContextBn128(
+  r1csFilePath: string,
+  wasmFilePath: string,
+  pkFilePath: string,
+  proofFilePath: string,
+  value: string,
+  context: Buffer,
+  seed: number,
+  reduce?: boolean | undefined | null,
+): void
+export function proveRangeWithContextBls12381(
+  r1csFilePath: string,
+  wasmFilePath: string,
+  pkFilePath: string,
+  proofFilePath: string,
+  value: string,
+  context: Buffer,
+  seed: number,
+  reduce?: boolean | undefined | null,
+): void
+export function verifyWithContextBn128(
+  vkFilePath: string,
+  proofFilePath: string,
+  context: Buffer,
+  publicInputs?: Array<string> | undefined | null,
+): boolean
+export function verifyWithContextBls12381(
+  vkFilePath: string,
+  proofFilePath: string,
+  context: Buffer,
+  publicInputs?: Array<string> | undefined | null,
+): boolean
+export function verifyWithContextBls12377(
+  vkFilePath: string,
+  proofFilePath: string,
+  context: Buffer,
+  publicInputs?: Array<string> | undefined | null,
+): boolean
+export function recoverAggregatedCommitmentUpdate(aggregatedCommitmentFilePath: string): string
 export function calculatePedersenCommitmentBn128(provingFilePath: string, m: string, v: string): string
+export function calculateVectorPedersenCommitmentBn128(vkFilePath: string, m: Array<string>, v: string): string
+export function calculateVectorPedersenCommitmentBls12381(vkFilePath: string, m: Array<string>, v: string): string
+export function calculateVectorPedersenCommitmentBls12377(vkFilePath: string, m: Array<string>, v: string): string
+export function checkPedersenCommitmentBn128(vkFilePath: string, proofFilePath: string, openingKeyFilePath: string): boolean
+export function checkPedersenCommitmentBls12381(
+  vkFilePath: string,
+  proofFilePath: string,
+  openingKeyFilePath: string,
+): boolean
+export function checkPedersenCommitmentBls12377(
+  vkFilePath: string,
+  proofFilePath: string,
+  openingKeyFilePath: string,
+): boolean
+export function setupRangeCircuitsBn128(
+  circuitDir: string,
+  keyDir: string,
+  seed: number,
+  bitWidths?: Array<number> | undefined | null,
+): void
+export function setupRangeCircuitsBls12381(
+  circuitDir: string,
+  keyDir: string,
+  seed: number,
+  bitWidths?: Array<number> | undefined | null,
+): void
+export function proveRange(
+  curve: string,
+  circuitDir: string,
+  keyDir: string,
+  proofFilePath: string,
+  value: string,
+  min: string,
+  max: string,
+  seed: number,
+  reduce?: boolean | undefined | null,
+): void
+export function proveRangeBitsBn128(
+  circuitDir: string,
+  keyDir: string,
+  proofFilePath: string,
+  value: string,
+  bitWidth: number,
+  seed: number,
+  reduce?: boolean | undefined | null,
+): void
+export function proveRangeBitsBls12381(
+  circuitDir: string,
+  keyDir: string,
+  proofFilePath: string,
+  value: string,
+  bitWidth: number,
+  seed: number,
+  reduce?: boolean | undefined | null,
+): void
+export function verifyRangeBitsBn128(keyDir: string, proofFilePath: string, bitWidth: number): boolean
+export function verifyRangeBitsBls12381(keyDir: string, proofFilePath: string, bitWidth: number): boolean
+export function verifyRangeBetweenBn128(keyDir: string, proofFilePath: string, min: string, max: string): boolean
+export function verifyRangeBetweenBls12381(keyDir: string, proofFilePath: string, min: string, max: string): boolean
+export function proveRangeVectorBn128(
+  r1CsFilePath: string,
+  wasmFilePath: string,
+  pkFilePath: string,
+  proofFilePath: string,
+  values: Array<string>,
+  seed: number,
+  reduce?: boolean | undefined | null,
+): void
+export function proveRangeVectorBls12381(
+  r1CsFilePath: string,
+  wasmFilePath: string,
+  pkFilePath: string,
+  proofFilePath: string,
+  values: Array<string>,
+  seed: number,
+  reduce?: boolean | undefined | null,
+): void
+export function proveSignalsBn128(
+  r1CsFilePath: string,
+  wasmFilePath: string,
+  symFilePath: string,
+  pkFilePath: string,
+  proofFilePath: string,
+  signals: Array<string>,
+  inputs: Record<string, Array<string>>,
+  seed: number,
+  reduce?: boolean | undefined | null,
+): string
+export function proveSignalsBls12381(
+  r1CsFilePath: string,
+  wasmFilePath: string,
+  symFilePath: string,
+  pkFilePath: string,
+  proofFilePath: string,
+  signals: Array<string>,
+  inputs: Record<string, Array<string>>,
+  seed: number,
+  reduce?: boolean | undefined | null,
+): string
+export function proveFromBuffersBn128(
+  r1Cs: Buffer,
+  wasm: Buffer,
+  pkFilePath: string,
+  proofFilePath: string,
+  commitWitnessCount: number,
+  inputs: Record<string, Array<string>>,
+  seed: number,
+  reduce?: boolean | undefined | null,
+): string
+export function proveFromBuffersBls12381(
+  r1Cs: Buffer,
+  wasm: Buffer,
+  pkFilePath: string,
+  proofFilePath: string,
+  commitWitnessCount: number,
+  inputs: Record<string, Array<string>>,
+  seed: number,
+  reduce?: boolean | undefined | null,
+): string
+export function proveFromKeyBuffersBn128(
+  r1Cs: Buffer,
+  wasm: Buffer,
+  pk: Buffer,
+  commitWitnessCount: number,
+  inputs: Record<string, Array<string>>,
+  seed: number,
+  reduce?: boolean | undefined | null,
+): ProofBuffer
+export function proveFromKeyBuffersBls12381(
+  r1Cs: Buffer,
+  wasm: Buffer,
+  pk: Buffer,
+  commitWitnessCount: number,
+  inputs: Record<string, Array<string>>,
+  seed: number,
+  reduce?: boolean | undefined | null,
+): ProofBuffer
+export function setupWithHashedLinkGeneratorsBn128(
+  r1CsFilePath: string,
+  commitWitnessCount: number,
+  seed: number,
+  label: string,
+  pathPk: string,
+  pathVk: string,
+  pathLinkPk: string,
+  pathLinkVk: string,
+): void
+export function setupWithHashedLinkGeneratorsBls12381(
+  r1CsFilePath: string,
+  commitWitnessCount: number,
+  seed: number,
+  label: string,
+  pathPk: string,
+  pathVk: string,
+  pathLinkPk: string,
+  pathLinkVk: string,
+): void
+export function verifyLinkGeneratorsBn128(linkVkFilePath: string, label: string): boolean
+export function verifyLinkGeneratorsBls12381(linkVkFilePath: string, label: string): boolean
+export function setupWithLinkGeneratorsBn128(
+  r1CsFilePath: string,
+  commitWitnessCount: number,
+  seed: number,
+  linkGenerators: Buffer,
+  pathPk: string,
+  pathVk: string,
+  pathLinkPk: string,
+  pathLinkVk: string,
+): void
+export function setupWithLinkGeneratorsBls12381(
+  r1CsFilePath: string,
+  commitWitnessCount: number,
+  seed: number,
+  linkGenerators: Buffer,
+  pathPk: string,
+  pathVk: string,
+  pathLinkPk: string,
+  pathLinkVk: string,
+): void
+export function verifyProvidedLinkGeneratorsBn128(linkVkFilePath: string, linkGenerators: Buffer): boolean
+export function verifyProvidedLinkGeneratorsBls12381(linkVkFilePath: string, linkGenerators: Buffer): boolean
+export function setupNativeRangeBn128(
+  bitWidth: number,
+  valueCount: number,
+  seed: number,
+  pkFilePath: string,
+  vkFilePath: string,
+): void
+export function setupNativeRangeBls12381(
+  bitWidth: number,
+  valueCount: number,
+  seed: number,
+  pkFilePath: string,
+  vkFilePath: string,
+): void
+export function setupNativeRangeBls12377(
+  bitWidth: number,
+  valueCount: number,
+  seed: number,
+  pkFilePath: string,
+  vkFilePath: string,
+): void
+export function proveNativeRangeBn128(
+  pkFilePath: string,
+  proofFilePath: string,
+  bitWidth: number,
+  values: Array<string>,
+  seed: number,
+  reduce?: boolean | undefined | null,
+): void
+export function proveNativeRangeBls12381(
+  pkFilePath: string,
+  proofFilePath: string,
+  bitWidth: number,
+  values: Array<string>,
+  seed: number,
+  reduce?: boolean | undefined | null,
+): void
+export function proveNativeRangeBls12377(
+  pkFilePath: string,
+  proofFilePath: string,
+  bitWidth: number,
+  values: Array<string>,
+  seed: number,
+  reduce?: boolean | undefined | null,
+): void
+// "0x" prefixed strings are hex, unprefixed ones are decimal ("10" is ten, not sixteen)
+export function parseFieldElementBn128(value: string, reduce?: boolean | undefined | null): string
+export function parseFieldElementBls12381(value: string, reduce?: boolean | undefined | null): string
+export function parseFieldElementBls12377(value: string, reduce?: boolean | undefined | null): string
+export function fieldElementFromBytesBn128(
+  bytes: Buffer,
+  littleEndian?: boolean | undefined | null,
+  reduce?: boolean | undefined | null,
+): string
+export function fieldElementFromBytesBls12381(
+  bytes: Buffer,
+  littleEndian?: boolean | undefined | null,
+  reduce?: boolean | undefined | null,
+): string
+export function fieldElementFromBytesBls12377(
+  bytes: Buffer,
+  littleEndian?: boolean | undefined | null,
+  reduce?: boolean | undefined | null,
+): string
+export class PedersenCommitmentBn128 {
+  static fromProofFile(proofFilePath: string): PedersenCommitmentBn128
+  static fromFile(path: string): PedersenCommitmentBn128
+  static fromBuffer(bytes: Buffer): PedersenCommitmentBn128
+  static fromOpeningKey(vkFilePath: string, openingKey: OpeningKeyBn128): PedersenCommitmentBn128
+  add(other: PedersenCommitmentBn128): PedersenCommitmentBn128
+  sub(other: PedersenCommitmentBn128): PedersenCommitmentBn128
+  negate(): PedersenCommitmentBn128
+  mul(scalar: string): PedersenCommitmentBn128
+  equals(other: PedersenCommitmentBn128): boolean
+  toBuffer(): Buffer
+  save(path: string): void
+  toString(): string
+}
+export class OpeningKeyBn128 {
+  constructor(m: Array<string>, v: string)
+  static fromJson(json: string): OpeningKeyBn128
+  static fromFile(path: string): OpeningKeyBn128
+  get m(): Array<string>
+  get v(): string
+  add(other: OpeningKeyBn128): OpeningKeyBn128
+  sub(other: OpeningKeyBn128): OpeningKeyBn128
+  negate(): OpeningKeyBn128
+  mul(scalar: string): OpeningKeyBn128
+  equals(other: OpeningKeyBn128): boolean
+  toJson(): string
+  save(path: string): void
+}
+export class PedersenCommitmentBls12381 {
+  static fromProofFile(proofFilePath: string): PedersenCommitmentBls12381
+  static fromFile(path: string): PedersenCommitmentBls12381
+  static fromBuffer(bytes: Buffer): PedersenCommitmentBls12381
+  static fromOpeningKey(vkFilePath: string, openingKey: OpeningKeyBls12381): PedersenCommitmentBls12381
+  add(other: PedersenCommitmentBls12381): PedersenCommitmentBls12381
+  sub(other: PedersenCommitmentBls12381): PedersenCommitmentBls12381
+  negate(): PedersenCommitmentBls12381
+  mul(scalar: string): PedersenCommitmentBls12381
+  equals(other: PedersenCommitmentBls12381): boolean
+  toBuffer(): Buffer
+  save(path: string): void
+  toString(): string
+}
+export class PedersenCommitmentBls12377 {
+  static fromProofFile(proofFilePath: string): PedersenCommitmentBls12377
+  static fromFile(path: string): PedersenCommitmentBls12377
+  static fromBuffer(bytes: Buffer): PedersenCommitmentBls12377
+  static fromOpeningKey(vkFilePath: string, openingKey: OpeningKeyBls12377): PedersenCommitmentBls12377
+  add(other: PedersenCommitmentBls12377): PedersenCommitmentBls12377
+  sub(other: PedersenCommitmentBls12377): PedersenCommitmentBls12377
+  negate(): PedersenCommitmentBls12377
+  mul(scalar: string): PedersenCommitmentBls12377
+  equals(other: PedersenCommitmentBls12377): boolean
+  toBuffer(): Buffer
+  save(path: string): void
+  toString(): string
+}
+export class OpeningKeyBls12381 {
+  constructor(m: Array<string>, v: string)
+  static fromJson(json: string): OpeningKeyBls12381
+  static fromFile(path: string): OpeningKeyBls12381
+  get m(): Array<string>
+  get v(): string
+  add(other: OpeningKeyBls12381): OpeningKeyBls12381
+  sub(other: OpeningKeyBls12381): OpeningKeyBls12381
+  negate(): OpeningKeyBls12381
+  mul(scalar: string): OpeningKeyBls12381
+  equals(other: OpeningKeyBls12381): boolean
+  toJson(): string
+  save(path: string): void
+}
+export class OpeningKeyBls12377 {
+  constructor(m: Array<string>, v: string)
+  static fromJson(json: string): OpeningKeyBls12377
+  static fromFile(path: string): OpeningKeyBls12377
+  get m(): Array<string>
+  get v(): string
+  add(other: OpeningKeyBls12377): OpeningKeyBls12377
+  sub(other: OpeningKeyBls12377): OpeningKeyBls12377
+  negate(): OpeningKeyBls12377
+  mul(scalar: string): OpeningKeyBls12377
+  equals(other: OpeningKeyBls12377): boolean
+  toJson(): string
+  save(path: string): void
+}
+export class AggregateLedgerBn128 {
+  constructor()
+  static load(path: string): AggregateLedgerBn128
+  save(path: string): void
+  addMember(id: string, proofFilePath: string, openingKeyFilePath?: string | undefined | null): void
+  removeMember(id: string): void
+  updateMember(id: string, proofFilePath: string, openingKeyFilePath?: string | undefined | null): void
+  memberIds(): Array<string>
+  memberCommitment(id: string): PedersenCommitmentBn128 | null
+  aggregate(): PedersenCommitmentBn128
+  recomputeAggregate(): PedersenCommitmentBn128
+  aggregateOpeningKey(): OpeningKeyBn128 | null
+  check(vkFilePath?: string | undefined | null): boolean
+  repair(): void
+}
+export class AggregateLedgerBls12381 {
+  constructor()
+  static load(path: string): AggregateLedgerBls12381
+  save(path: string): void
+  addMember(id: string, proofFilePath: string, openingKeyFilePath?: string | undefined | null): void
+  removeMember(id: string): void
+  updateMember(id: string, proofFilePath: string, openingKeyFilePath?: string | undefined | null): void
+  memberIds(): Array<string>
+  memberCommitment(id: string): PedersenCommitmentBls12381 | null
+  aggregate(): PedersenCommitmentBls12381
+  recomputeAggregate(): PedersenCommitmentBls12381
+  aggregateOpeningKey(): OpeningKeyBls12381 | null
+  check(vkFilePath?: string | undefined | null): boolean
+  repair(): void
+}
+export class AggregateLedgerBls12377 {
+  constructor()
+  static load(path: string): AggregateLedgerBls12377
+  save(path: string): void
+  addMember(id: string, proofFilePath: string, openingKeyFilePath?: string | undefined | null): void
+  removeMember(id: string): void
+  updateMember(id: string, proofFilePath: string, openingKeyFilePath?: string | undefined | null): void
+  memberIds(): Array<string>
+  memberCommitment(id: string): PedersenCommitmentBls12377 | null
+  aggregate(): PedersenCommitmentBls12377
+  recomputeAggregate(): PedersenCommitmentBls12377
+  aggregateOpeningKey(): OpeningKeyBls12377 | null
+  check(vkFilePath?: string | undefined | null): boolean
+  repair(): void
+}
+export class OpeningProofBn128 {
+  static prove(vkFilePath: string, openingKey: OpeningKeyBn128, context?: Buffer | undefined | null): OpeningProofBn128
+  static fromBuffer(bytes: Buffer): OpeningProofBn128
+  static fromJson(json: string): OpeningProofBn128
+  verify(vkFilePath: string, commitment: PedersenCommitmentBn128, context?: Buffer | undefined | null): boolean
+  toBuffer(): Buffer
+  toJson(): string
+}
+export class OpeningProofBls12381 {
+  static prove(vkFilePath: string, openingKey: OpeningKeyBls12381, context?: Buffer | undefined | null): OpeningProofBls12381
+  static fromBuffer(bytes: Buffer): OpeningProofBls12381
+  static fromJson(json: string): OpeningProofBls12381
+  verify(vkFilePath: string, commitment: PedersenCommitmentBls12381, context?: Buffer | undefined | null): boolean
+  toBuffer(): Buffer
+  toJson(): string
+}
+export class OpeningProofBls12377 {
+  static prove(vkFilePath: string, openingKey: OpeningKeyBls12377, context?: Buffer | undefined | null): OpeningProofBls12377
+  static fromBuffer(bytes: Buffer): OpeningProofBls12377
+  static fromJson(json: string): OpeningProofBls12377
+  verify(vkFilePath: string, commitment: PedersenCommitmentBls12377, context?: Buffer | undefined | null): boolean
+  toBuffer(): Buffer
+  toJson(): string
+}
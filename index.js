@@ -237,35 +237,239 @@ if (!nativeBinding) {
 const {
   setupFromCircomR1CsBn128,
   setupFromCircomR1CsBls12381,
+  setupFromCircomR1CsBufferBn128,
+  setupFromCircomR1CsBufferBls12381,
   proveRangeBn128,
   proveRangeBls12381,
   verifyRangeBn128,
   verifyRangeBls12381,
+  verifyRangeBls12377,
   getProofBn128,
   aggregateProofCommitmentBn128,
   aggregateProofCommitmentBls12381,
+  aggregateProofCommitmentBls12377,
   getAggregatedCommitmentBn128,
   getAggregatedCommitmentBls12381,
+  getAggregatedCommitmentBls12377,
   aggregateOpeningKeysBn128,
   aggregateOpeningKeysBls12381,
+  aggregateOpeningKeysBls12377,
   updateAggregatedCommitmentBn128,
   updateAggregatedCommitmentBls12381,
+  removeFromAggregateBn128,
+  removeFromAggregateBls12381,
+  removeFromAggregateBls12377,
+  proveAggregatedTotalBn128,
+  proveAggregatedTotalBls12381,
+  proveAggregatedTotalBls12377,
+  verifyAggregatedTotalBn128,
+  verifyAggregatedTotalBls12381,
+  verifyAggregatedTotalBls12377,
+  proveAggregateRangeBn128,
+  proveAggregateRangeBls12381,
+  verifyAggregateRangeBn128,
+  verifyAggregateRangeBls12381,
+  verifyAggregateRangeBls12377,
+  proveCommitmentEqualityBn128,
+  proveCommitmentEqualityBls12381,
+  proveCommitmentEqualityBls12377,
+  verifyCommitmentEqualityBn128,
+  verifyCommitmentEqualityBls12381,
+  verifyCommitmentEqualityBls12377,
+  setupInsecureAggregationSrsBn128,
+  setupInsecureAggregationSrsBls12381,
+  setupInsecureAggregationSrsBls12377,
+  aggregateProofsBn128,
+  aggregateProofsBls12381,
+  aggregateProofsBls12377,
+  verifyAggregatedProofsBn128,
+  verifyAggregatedProofsBls12381,
+  verifyAggregatedProofsBls12377,
+  rerandomizeProofBn128,
+  rerandomizeProofBls12381,
+  rerandomizeProofBls12377,
+  contextToFieldBn128,
+  contextToFieldBls12381,
+  contextToFieldBls12377,
+  proveRangeWithContextBn128,
+  proveRangeWithContextBls12381,
+  verifyWithContextBn128,
+  verifyWithContextBls12381,
+  verifyWithContextBls12377,
+  recoverAggregatedCommitmentUpdate,
   calculatePedersenCommitmentBn128,
+  calculateVectorPedersenCommitmentBn128,
+  calculateVectorPedersenCommitmentBls12381,
+  calculateVectorPedersenCommitmentBls12377,
+  checkPedersenCommitmentBn128,
+  checkPedersenCommitmentBls12381,
+  checkPedersenCommitmentBls12377,
+  setupRangeCircuitsBn128,
+  setupRangeCircuitsBls12381,
+  proveRange,
+  proveRangeBitsBn128,
+  proveRangeBitsBls12381,
+  verifyRangeBitsBn128,
+  verifyRangeBitsBls12381,
+  verifyRangeBetweenBn128,
+  verifyRangeBetweenBls12381,
+  proveRangeVectorBn128,
+  proveRangeVectorBls12381,
+  proveSignalsBn128,
+  proveSignalsBls12381,
+  proveFromBuffersBn128,
+  proveFromBuffersBls12381,
+  proveFromKeyBuffersBn128,
+  proveFromKeyBuffersBls12381,
+  setupWithHashedLinkGeneratorsBn128,
+  setupWithHashedLinkGeneratorsBls12381,
+  verifyLinkGeneratorsBn128,
+  verifyLinkGeneratorsBls12381,
+  setupWithLinkGeneratorsBn128,
+  setupWithLinkGeneratorsBls12381,
+  verifyProvidedLinkGeneratorsBn128,
+  verifyProvidedLinkGeneratorsBls12381,
+  setupNativeRangeBn128,
+  setupNativeRangeBls12381,
+  setupNativeRangeBls12377,
+  proveNativeRangeBn128,
+  proveNativeRangeBls12381,
+  proveNativeRangeBls12377,
+  parseFieldElementBn128,
+  parseFieldElementBls12381,
+  parseFieldElementBls12377,
+  fieldElementFromBytesBn128,
+  fieldElementFromBytesBls12381,
+  fieldElementFromBytesBls12377,
+  PedersenCommitmentBn128,
+  OpeningKeyBn128,
+  PedersenCommitmentBls12381,
+  PedersenCommitmentBls12377,
+  OpeningKeyBls12381,
+  OpeningKeyBls12377,
+  AggregateLedgerBn128,
+  AggregateLedgerBls12381,
+  AggregateLedgerBls12377,
+  OpeningProofBn128,
+  OpeningProofBls12381,
+  OpeningProofBls12377,
 } = nativeBinding
 
 module.exports.setupFromCircomR1CsBn128 = setupFromCircomR1CsBn128
 module.exports.setupFromCircomR1CsBls12381 = setupFromCircomR1CsBls12381
+module.exports.setupFromCircomR1CsBufferBn128 = setupFromCircomR1CsBufferBn128
+module.exports.setupFromCircomR1CsBufferBls12381 = setupFromCircomR1CsBufferBls12381
 module.exports.proveRangeBn128 = proveRangeBn128
 module.exports.proveRangeBls12381 = proveRangeBls12381
 module.exports.verifyRangeBn128 = verifyRangeBn128
 module.exports.verifyRangeBls12381 = verifyRangeBls12381
+module.exports.verifyRangeBls12377 = verifyRangeBls12377
 module.exports.getProofBn128 = getProofBn128
 module.exports.aggregateProofCommitmentBn128 = aggregateProofCommitmentBn128
 module.exports.aggregateProofCommitmentBls12381 = aggregateProofCommitmentBls12381
+module.exports.aggregateProofCommitmentBls12377 = aggregateProofCommitmentBls12377
 module.exports.getAggregatedCommitmentBn128 = getAggregatedCommitmentBn128
 module.exports.getAggregatedCommitmentBls12381 = getAggregatedCommitmentBls12381
+module.exports.getAggregatedCommitmentBls12377 = getAggregatedCommitmentBls12377
 module.exports.aggregateOpeningKeysBn128 = aggregateOpeningKeysBn128
 module.exports.aggregateOpeningKeysBls12381 = aggregateOpeningKeysBls12381
+module.exports.aggregateOpeningKeysBls12377 = aggregateOpeningKeysBls12377
 module.exports.updateAggregatedCommitmentBn128 = updateAggregatedCommitmentBn128
 module.exports.updateAggregatedCommitmentBls12381 = updateAggregatedCommitmentBls12381
+module.exports.removeFromAggregateBn128 = removeFromAggregateBn128
+module.exports.removeFromAggregateBls12381 = removeFromAggregateBls12381
+module.exports.removeFromAggregateBls12377 = removeFromAggregateBls12377
+module.exports.proveAggregatedTotalBn128 = proveAggregatedTotalBn128
+module.exports.proveAggregatedTotalBls12381 = proveAggregatedTotalBls12381
+module.exports.proveAggregatedTotalBls12377 = proveAggregatedTotalBls12377
+module.exports.verifyAggregatedTotalBn128 = verifyAggregatedTotalBn128
+module.exports.verifyAggregatedTotalBls12381 = verifyAggregatedTotalBls12381
+module.exports.verifyAggregatedTotalBls12377 = verifyAggregatedTotalBls12377
+module.exports.proveAggregateRangeBn128 = proveAggregateRangeBn128
+module.exports.proveAggregateRangeBls12381 = proveAggregateRangeBls12381
+module.exports.verifyAggregateRangeBn128 = verifyAggregateRangeBn128
+module.exports.verifyAggregateRangeBls12381 = verifyAggregateRangeBls12381
+module.exports.verifyAggregateRangeBls12377 = verifyAggregateRangeBls12377
+module.exports.proveCommitmentEqualityBn128 = proveCommitmentEqualityBn128
+module.exports.proveCommitmentEqualityBls12381 = proveCommitmentEqualityBls12381
+module.exports.proveCommitmentEqualityBls12377 = proveCommitmentEqualityBls12377
+module.exports.verifyCommitmentEqualityBn128 = verifyCommitmentEqualityBn128
+module.exports.verifyCommitmentEqualityBls12381 = verifyCommitmentEqualityBls12381
+module.exports.verifyCommitmentEqualityBls12377 = verifyCommitmentEqualityBls12377
+module.exports.setupInsecureAggregationSrsBn128 = setupInsecureAggregationSrsBn128
+module.exports.setupInsecureAggregationSrsBls12381 = setupInsecureAggregationSrsBls12381
+module.exports.setupInsecureAggregationSrsBls12377 = setupInsecureAggregationSrsBls12377
+module.exports.aggregateProofsBn128 = aggregateProofsBn128
+module.exports.aggregateProofsBls12381 = aggregateProofsBls12381
+module.exports.aggregateProofsBls12377 = aggregateProofsBls12377
+module.exports.verifyAggregatedProofsBn128 = verifyAggregatedProofsBn128
+module.exports.verifyAggregatedProofsBls12381 = verifyAggregatedProofsBls12381
+module.exports.verifyAggregatedProofsBls12377 = verifyAggregatedProofsBls12377
+module.exports.rerandomizeProofBn128 = rerandomizeProofBn128
+module.exports.rerandomizeProofBls12381 = rerandomizeProofBls12381
+module.exports.rerandomizeProofBls12377 = rerandomizeProofBls12377
+module.exports.contextToFieldBn128 = contextToFieldBn128
+module.exports.contextToFieldBls12381 = contextToFieldBls12381
+module.exports.contextToFieldBls12377 = contextToFieldBls12377
+module.exports.proveRangeWithContextBn128 = proveRangeWithContextBn128
+module.exports.proveRangeWithContextBls12381 = proveRangeWithContextBls12381
+module.exports.verifyWithContextBn128 = verifyWithContextBn128
+module.exports.verifyWithContextBls12381 = verifyWithContextBls12381
+module.exports.verifyWithContextBls12377 = verifyWithContextBls12377
+module.exports.recoverAggregatedCommitmentUpdate = recoverAggregatedCommitmentUpdate
 module.exports.calculatePedersenCommitmentBn128 = calculatePedersenCommitmentBn128
+module.exports.calculateVectorPedersenCommitmentBn128 = calculateVectorPedersenCommitmentBn128
+module.exports.calculateVectorPedersenCommitmentBls12381 = calculateVectorPedersenCommitmentBls12381
+module.exports.calculateVectorPedersenCommitmentBls12377 = calculateVectorPedersenCommitmentBls12377
+module.exports.checkPedersenCommitmentBn128 = checkPedersenCommitmentBn128
+module.exports.checkPedersenCommitmentBls12381 = checkPedersenCommitmentBls12381
+module.exports.checkPedersenCommitmentBls12377 = checkPedersenCommitmentBls12377
+module.exports.setupRangeCircuitsBn128 = setupRangeCircuitsBn128
+module.exports.setupRangeCircuitsBls12381 = setupRangeCircuitsBls12381
+module.exports.proveRange = proveRange
+module.exports.proveRangeBitsBn128 = proveRangeBitsBn128
+module.exports.proveRangeBitsBls12381 = proveRangeBitsBls12381
+module.exports.verifyRangeBitsBn128 = verifyRangeBitsBn128
+module.exports.verifyRangeBitsBls12381 = verifyRangeBitsBls12381
+module.exports.verifyRangeBetweenBn128 = verifyRangeBetweenBn128
+module.exports.verifyRangeBetweenBls12381 = verifyRangeBetweenBls12381
+module.exports.proveRangeVectorBn128 = proveRangeVectorBn128
+module.exports.proveRangeVectorBls12381 = proveRangeVectorBls12381
+module.exports.proveSignalsBn128 = proveSignalsBn128
+module.exports.proveSignalsBls12381 = proveSignalsBls12381
+module.exports.proveFromBuffersBn128 = proveFromBuffersBn128
+module.exports.proveFromBuffersBls12381 = proveFromBuffersBls12381
+module.exports.proveFromKeyBuffersBn128 = proveFromKeyBuffersBn128
+module.exports.proveFromKeyBuffersBls12381 = proveFromKeyBuffersBls12381
+module.exports.setupWithHashedLinkGeneratorsBn128 = setupWithHashedLinkGeneratorsBn128
+module.exports.setupWithHashedLinkGeneratorsBls12381 = setupWithHashedLinkGeneratorsBls12381
+module.exports.verifyLinkGeneratorsBn128 = verifyLinkGeneratorsBn128
+module.exports.verifyLinkGeneratorsBls12381 = verifyLinkGeneratorsBls12381
+module.exports.setupWithLinkGeneratorsBn128 = setupWithLinkGeneratorsBn128
+module.exports.setupWithLinkGeneratorsBls12381 = setupWithLinkGeneratorsBls12381
+module.exports.verifyProvidedLinkGeneratorsBn128 = verifyProvidedLinkGeneratorsBn128
+module.exports.verifyProvidedLinkGeneratorsBls12381 = verifyProvidedLinkGeneratorsBls12381
+module.exports.setupNativeRangeBn128 = setupNativeRangeBn128
+module.exports.setupNativeRangeBls12381 = setupNativeRangeBls12381
+module.exports.setupNativeRangeBls12377 = setupNativeRangeBls12377
+module.exports.proveNativeRangeBn128 = proveNativeRangeBn128
+module.exports.proveNativeRangeBls12381 = proveNativeRangeBls12381
+module.exports.proveNativeRangeBls12377 = proveNativeRangeBls12377
+module.exports.parseFieldElementBn128 = parseFieldElementBn128
+module.exports.parseFieldElementBls12381 = parseFieldElementBls12381
+module.exports.parseFieldElementBls12377 = parseFieldElementBls12377
+module.exports.fieldElementFromBytesBn128 = fieldElementFromBytesBn128
+module.exports.fieldElementFromBytesBls12381 = fieldElementFromBytesBls12381
+module.exports.fieldElementFromBytesBls12377 = fieldElementFromBytesBls12377
+module.exports.PedersenCommitmentBn128 = PedersenCommitmentBn128
+module.exports.OpeningKeyBn128 = OpeningKeyBn128
+module.exports.PedersenCommitmentBls12381 = PedersenCommitmentBls12381
+module.exports.PedersenCommitmentBls12377 = PedersenCommitmentBls12377
+module.exports.OpeningKeyBls12381 = OpeningKeyBls12381
+module.exports.OpeningKeyBls12377 = OpeningKeyBls12377
+module.exports.AggregateLedgerBn128 = AggregateLedgerBn128
+module.exports.AggregateLedgerBls12381 = AggregateLedgerBls12381
+module.exports.AggregateLedgerBls12377 = AggregateLedgerBls12377
+module.exports.OpeningProofBn128 = OpeningProofBn128
+module.exports.OpeningProofBls12381 = OpeningProofBls12381
+module.exports.OpeningProofBls12377 = OpeningProofBls12377
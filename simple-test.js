@@ -147,6 +147,18 @@ updateAggregatedCommitmentBn128(
     333111
 )
 
+// one value per committed witness, a single value for range_proof
+updateAggregatedCommitmentBn128(
+    "./circom/bn128/range_proof.r1cs",
+    "./range_pk.bin",
+    "./circom/bn128/range_proof.wasm",
+    "./test_proof3.bin",
+    "./aggregated_commitment.bin",
+    "./aggregated_opening_key.json",
+    ["0xfffffffffffffffe"],
+    333112
+)
+
 aggregatedOpeningKeyJson = JSON.parse(fs.readFileSync("./aggregated_opening_key.json"));
 console.log(
     "calculated aggregated commitment:",
@@ -0,0 +1,249 @@
+use ark_ec::{pairing::Pairing, CurveGroup};
+use ark_serialize::{CanonicalSerialize, CanonicalDeserialize};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use dock_crypto_utils::transcript::{new_merlin_transcript, Transcript};
+use legogroth16::{
+    aggregation::{
+        commitment::PairCommitment,
+        legogroth16::{aggregate_proofs, verify_aggregate_proof, AggregateLegoProof},
+        srs::{setup_fake_srs, GenericSRS},
+    },
+    prepare_verifying_key,
+    Proof,
+    VerifyingKey,
+};
+use std::fs::{read, write};
+
+use crate::{
+    commitment::Commitment,
+    error::Error,
+};
+
+pub const AGGREGATION_LABEL : &[u8] = b"napirs-legogroth16 aggregation";
+
+// snarkpack aggregation of proofs made with the same proving key
+//
+// proof  : logarithmic size inner pairing product argument for a, b, c and d of every proof
+// d      : the commitments of the proofs, the verifier checks
+//            sum d_i == aggregated commitment
+//            proof.com_d == commitment of d under the srs key
+//          the aggregation argument opens com_d, so the aggregated proof is bound to the aggregated commitment
+//
+// snarkpack needs a power of two number of proofs, the last proof is repeated up to the next one.
+// d only holds the original commitments, the padding is redone by the verifier
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct AggregatedProof<E:Pairing> {
+    pub proof : AggregateLegoProof<E>,
+    pub d : Vec<E::G1Affine>,
+}
+
+impl<E:Pairing> AggregatedProof<E> {
+    pub fn aggregated_commitment(&self) -> Commitment<E> {
+        Commitment::new((self.d.iter().fold(E::G1::default(), |sum, d| sum + d)).into_affine())
+    }
+
+    pub fn read_from_file(path : &str) -> Result<Self, Error> {
+        Ok(Self::deserialize_compressed(&*read(path)?)?)
+    }
+
+    pub fn write_to_file(&self, path : &str) -> Result<(), Error> {
+        let mut compressed_bytes:Vec<u8> = Vec::new();
+        self.serialize_compressed(&mut compressed_bytes)?;
+        write(path, compressed_bytes)?;
+        Ok(())
+    }
+}
+
+// INSECURE, for tests and development only
+// srs for up to max_proofs proofs (rounded up to a power of two), its secret exponents are derived
+// from the seed, whoever knows the seed can forge aggregated proofs that verify.
+// in production use an srs from a trusted setup ceremony, see read_aggregation_srs
+pub fn setup_insecure_aggregation_srs<E:Pairing>(
+    max_proofs : usize,
+    seed : u64,
+    srs_file_path : &str
+) -> Result<(), Error> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let srs = setup_fake_srs::<E, _>(&mut rng, padded_len(max_proofs));
+
+    let mut compressed_bytes:Vec<u8> = Vec::new();
+    srs.serialize_compressed(&mut compressed_bytes)?;
+    write(srs_file_path, compressed_bytes)?;
+    Ok(())
+}
+
+// srs file : GenericSRS, compressed arkworks serialization (e.g. converted from a ceremony transcript)
+pub fn read_aggregation_srs<E:Pairing>(
+    srs_file_path : &str
+) -> Result<GenericSRS<E>, Error> {
+    Ok(GenericSRS::<E>::deserialize_compressed(&*read(srs_file_path)?)?)
+}
+
+// public_inputs : one entry per proof, empty for circuits without public inputs
+pub fn aggregate<E:Pairing>(
+    srs : &GenericSRS<E>,
+    proofs : &[Proof<E>],
+    public_inputs : &[Vec<E::ScalarField>]
+) -> Result<AggregatedProof<E>, Error> {
+    check_public_inputs(proofs.len(), public_inputs)?;
+    let n = padded_len(proofs.len());
+    check_srs_len(srs, n)?;
+
+    let (prover_srs, _) = srs.specialize(n);
+    let padded_proofs = pad(proofs, n);
+    let padded_inputs = pad(public_inputs, n);
+    let padded_d = padded_proofs.iter().map(|proof| proof.d).collect::<Vec<_>>();
+
+    let mut transcript = aggregation_transcript::<E>(&padded_inputs, &padded_d);
+    let proof = aggregate_proofs(prover_srs, &mut transcript, &padded_proofs)?;
+
+    Ok(AggregatedProof {
+        proof,
+        d : proofs.iter().map(|proof| proof.d).collect(),
+    })
+}
+
+pub fn verify_aggregated<E:Pairing>(
+    srs : &GenericSRS<E>,
+    vk : &VerifyingKey<E>,
+    aggregated_proof : &AggregatedProof<E>,
+    public_inputs : &[Vec<E::ScalarField>],
+    aggregated_commitment : &Commitment<E>
+) -> Result<bool, Error> {
+    check_public_inputs(aggregated_proof.d.len(), public_inputs)?;
+    let n = padded_len(aggregated_proof.d.len());
+    check_srs_len(srs, n)?;
+
+    if aggregated_proof.aggregated_commitment() != *aggregated_commitment {
+        return Ok(false);
+    }
+
+    let (prover_srs, verifier_srs) = srs.specialize(n);
+    let padded_inputs = pad(public_inputs, n);
+    let padded_d = pad(&aggregated_proof.d, n);
+
+    // the aggregated proof must commit to the same d
+    if PairCommitment::<E>::single(&prover_srs.vkey, &padded_d)? != aggregated_proof.proof.com_d {
+        return Ok(false);
+    }
+
+    let mut transcript = aggregation_transcript::<E>(&padded_inputs, &padded_d);
+    let verified = verify_aggregate_proof(
+        &verifier_srs,
+        &prepare_verifying_key(vk),
+        &padded_inputs,
+        &aggregated_proof.proof,
+        StdRng::from_entropy(),
+        &mut transcript,
+        None
+    );
+    Ok(verified.is_ok())
+}
+
+// everything outside of the aggregation that the proof is bound to
+fn aggregation_transcript<E:Pairing>(
+    public_inputs : &[Vec<E::ScalarField>],
+    d : &[E::G1Affine]
+) -> impl Transcript {
+    let mut transcript = new_merlin_transcript(AGGREGATION_LABEL);
+    transcript.append(b"public-inputs", &public_inputs.to_vec());
+    transcript.append(b"d", &d.to_vec());
+    transcript
+}
+
+fn padded_len(count : usize) -> usize {
+    count.max(2).next_power_of_two()
+}
+
+fn pad<T:Clone>(items : &[T], n : usize) -> Vec<T> {
+    let mut padded = items.to_vec();
+    padded.resize(n, items[items.len() - 1].clone());
+    padded
+}
+
+fn check_public_inputs<F>(proof_count : usize, public_inputs : &[Vec<F>]) -> Result<(), Error> {
+    if proof_count == 0 {
+        return Err(Error::Aggregation("no proofs to aggregate".to_string()));
+    }
+    if public_inputs.len() != proof_count {
+        return Err(Error::Aggregation(format!(
+            "{} proofs and {} public inputs",
+            proof_count,
+            public_inputs.len()
+        )));
+    }
+    Ok(())
+}
+
+// specialize panics on a too small srs
+fn check_srs_len<E:Pairing>(srs : &GenericSRS<E>, n : usize) -> Result<(), Error> {
+    if srs.g_alpha_powers.len() < 2 * n {
+        return Err(Error::Aggregation(format!(
+            "srs supports up to {} proofs, {} needed",
+            srs.g_alpha_powers.len() / 2,
+            n
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Bn254;
+    use legogroth16::ProvingKey;
+    use crate::circuit::test_util::{prove_values, range_pk};
+
+    fn insecure_srs(max_proofs : usize) -> GenericSRS<Bn254> {
+        setup_fake_srs::<Bn254, _>(&mut StdRng::seed_from_u64(0), padded_len(max_proofs))
+    }
+
+    fn proofs(pk : &ProvingKey<Bn254>, values : &[u64]) -> Vec<Proof<Bn254>> {
+        values
+            .iter()
+            .map(|value| prove_values(pk, &[*value], *value).0)
+            .collect()
+    }
+
+    #[test]
+    fn aggregated_proof_round_trip() {
+        let pk = range_pk(1);
+        let srs = insecure_srs(4);
+        let proofs = proofs(&pk, &[1, 2, 3]);
+        let public_inputs = vec![Vec::new(); proofs.len()];
+
+        let aggregated_proof = aggregate::<Bn254>(&srs, &proofs, &public_inputs).unwrap();
+        let mut bytes = Vec::new();
+        aggregated_proof.serialize_compressed(&mut bytes).unwrap();
+        let aggregated_proof = AggregatedProof::<Bn254>::deserialize_compressed(&*bytes).unwrap();
+
+        let aggregated_commitment = proofs
+            .iter()
+            .fold(Commitment::new(<Bn254 as Pairing>::G1Affine::default()), |sum, proof| sum.add(&Commitment::from_proof(proof)));
+        assert!(verify_aggregated::<Bn254>(&srs, &pk.vk, &aggregated_proof, &public_inputs, &aggregated_commitment).unwrap());
+    }
+
+    #[test]
+    fn aggregated_proof_is_bound_to_d() {
+        let pk = range_pk(1);
+        let srs = insecure_srs(4);
+        let proofs = proofs(&pk, &[1, 2, 3]);
+        let other = self::proofs(&pk, &[4]);
+        let public_inputs = vec![Vec::new(); proofs.len()];
+        let aggregated_proof = aggregate::<Bn254>(&srs, &proofs, &public_inputs).unwrap();
+
+        // same sum, other members
+        let mut swapped = aggregated_proof.clone();
+        let shift = Commitment::<Bn254>::from_proof(&other[0]);
+        swapped.d[0] = Commitment::<Bn254>::new(swapped.d[0]).add(&shift).point;
+        swapped.d[1] = Commitment::<Bn254>::new(swapped.d[1]).sub(&shift).point;
+        assert!(!verify_aggregated::<Bn254>(&srs, &pk.vk, &swapped, &public_inputs, &aggregated_proof.aggregated_commitment()).unwrap());
+
+        // other aggregated commitment
+        let aggregated_commitment = aggregated_proof.aggregated_commitment().add(&shift);
+        assert!(!verify_aggregated::<Bn254>(&srs, &pk.vk, &aggregated_proof, &public_inputs, &aggregated_commitment).unwrap());
+
+        assert!(verify_aggregated::<Bn254>(&srs, &pk.vk, &aggregated_proof, &[Vec::new()], &aggregated_proof.aggregated_commitment()).is_err());
+        assert!(aggregate::<Bn254>(&insecure_srs(2), &proofs, &public_inputs).is_err());
+    }
+}
@@ -0,0 +1,536 @@
+//! legogroth16 command-line tool
+//!
+//! every command prints a json object on stdout
+//! exit codes
+//!  0 : success
+//!  1 : the proof does not verify / the opening key does not open the commitment
+//!  2 : error, {"error" : message} on stderr
+//! warnings are printed on stderr as {"warning" : message}
+
+use ark_bls12_377::Bls12_377;
+use ark_bls12_381::Bls12_381;
+use ark_bn254::Bn254;
+use ark_ec::pairing::Pairing;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::{rngs::StdRng, Rng, SeedableRng};
+use clap::{Parser, Subcommand, ValueEnum};
+use hex::ToHex;
+use legogroth16::{Proof, ProvingKey, VerifyingKey, VerifyingKeyWithLink};
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    fs::read,
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+use napirs_legogroth16::{
+    commitment::{Commitment, OpeningKey},
+    context::context_to_field,
+    error::Error,
+    field::{field_element_to_hex, parse_field_element, ReduceMode},
+    keys::{link_generators_path, write_to_file_compresed_proving_key},
+    prover::{create_proof, proof_to_bytes, prove_signals, read_proof_from_file, update_aggregated_commitment},
+    range::{prove_native_range, setup_native_range},
+    setup::{read_link_generators_from_file, setup_from_circom_r1cs, setup_from_circom_r1cs_with_link, LinkGenerators},
+    verifier::{verify_circuit_proof, verify_link_generators, verify_provided_link_generators},
+};
+
+#[derive(Parser)]
+#[command(name = "legogroth16", about = "legogroth16 proofs with pedersen commitments")]
+struct Cli {
+    #[arg(long, value_enum, default_value = "bn128", global = true)]
+    curve : Curve,
+
+    #[command(subcommand)]
+    command : Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Curve {
+    Bn128,
+    #[value(name = "bls12-381")]
+    Bls12_381,
+    /// native circuits only (setup-range / prove-range), circom files are bn128 / bls12-381
+    #[value(name = "bls12-377")]
+    Bls12_377,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// proving and verifying keys from a circom r1cs file
+    Setup {
+        #[arg(long)]
+        r1cs : PathBuf,
+        #[arg(long)]
+        pk : PathBuf,
+        #[arg(long)]
+        vk : PathBuf,
+        #[arg(long, default_value_t = 1)]
+        commit_witness_count : usize,
+        /// random if not set, a seeded setup is insecure (the trapdoor follows from the seed)
+        #[arg(long)]
+        seed : Option<u64>,
+        /// hash the link generators from this public label instead of sampling them from the seed
+        #[arg(long, requires_all = ["link_pk", "link_vk"], conflicts_with = "link_generators")]
+        link_label : Option<String>,
+        /// link generators file (compressed LinkPublicGenerators) instead of sampling them from the seed
+        #[arg(long, requires_all = ["link_pk", "link_vk"])]
+        link_generators : Option<PathBuf>,
+        /// link proving key (with the link generators), written with --link-label or --link-generators,
+        /// the generators are recorded next to it in <link_pk>_generators.bin
+        #[arg(long)]
+        link_pk : Option<PathBuf>,
+        #[arg(long)]
+        link_vk : Option<PathBuf>,
+    },
+    /// keys of the native range circuit, value_count values of bits bits per proof, no r1cs needed
+    SetupRange {
+        #[arg(long)]
+        bits : usize,
+        #[arg(long, default_value_t = 1)]
+        value_count : usize,
+        #[arg(long)]
+        pk : PathBuf,
+        #[arg(long)]
+        vk : PathBuf,
+        /// random if not set, a seeded setup is insecure (the trapdoor follows from the seed)
+        #[arg(long)]
+        seed : Option<u64>,
+    },
+    /// proof of the native range circuit, the opening key is written to <proof>_opening_key.json
+    ProveRange {
+        #[arg(long)]
+        pk : PathBuf,
+        #[arg(long)]
+        proof : PathBuf,
+        #[arg(long)]
+        bits : usize,
+        /// committed value (repeatable, value_count of the setup)
+        #[arg(long = "value", required = true)]
+        values : Vec<String>,
+        #[arg(long)]
+        reduce : bool,
+        #[arg(long)]
+        seed : Option<u64>,
+    },
+    /// check that the link generators of a link verifying key are hashed from the label
+    /// or are the ones of the generators file, exit code 1 if they are not
+    CheckLink {
+        #[arg(long)]
+        link_vk : PathBuf,
+        #[arg(long, required_unless_present = "generators", conflicts_with = "generators")]
+        label : Option<String>,
+        #[arg(long)]
+        generators : Option<PathBuf>,
+    },
+    /// proof of a circom circuit, the opening key defaults to <proof>_opening_key.json
+    Prove {
+        #[arg(long)]
+        r1cs : PathBuf,
+        #[arg(long)]
+        wasm : PathBuf,
+        #[arg(long)]
+        pk : PathBuf,
+        #[arg(long)]
+        proof : PathBuf,
+        /// circuit input, name=value or name=value,value,... for arrays (repeatable)
+        #[arg(long = "input", required = true)]
+        inputs : Vec<String>,
+        #[arg(long, default_value_t = 1)]
+        commit_witness_count : usize,
+        #[arg(long)]
+        opening_key : Option<PathBuf>,
+        /// .sym file, the committed signals are checked against it (requires --signal)
+        #[arg(long, requires = "signals")]
+        sym : Option<PathBuf>,
+        /// committed signal in order (repeatable), the opening key is keyed by signal name
+        #[arg(long = "signal", requires = "sym")]
+        signals : Vec<String>,
+        /// reduce values outside of [0, p) instead of rejecting them
+        #[arg(long)]
+        reduce : bool,
+        #[arg(long)]
+        seed : Option<u64>,
+    },
+    /// verify a proof, exit code 1 if it does not verify
+    Verify {
+        #[arg(long)]
+        vk : PathBuf,
+        #[arg(long)]
+        proof : PathBuf,
+        /// public input in circuit order (repeatable)
+        #[arg(long = "public-input")]
+        public_inputs : Vec<String>,
+        /// context the proof is bound to, appended as the last public input
+        #[arg(long)]
+        context : Option<String>,
+    },
+    /// print the content of a key, proof, commitment or opening key file
+    Inspect {
+        #[arg(value_enum)]
+        kind : Kind,
+        file : PathBuf,
+    },
+    /// sum of proof.d of the proofs, and optionally of their opening keys
+    Aggregate {
+        #[arg(long = "proof", required = true)]
+        proofs : Vec<PathBuf>,
+        #[arg(long)]
+        out : PathBuf,
+        #[arg(long = "opening-key")]
+        opening_keys : Vec<PathBuf>,
+        #[arg(long, requires = "opening_keys")]
+        opening_key_out : Option<PathBuf>,
+    },
+    /// check an opening key against a commitment or proof.d, exit code 1 if it does not open it
+    Open {
+        #[arg(long)]
+        vk : PathBuf,
+        #[arg(long)]
+        opening_key : PathBuf,
+        #[arg(long, required_unless_present = "proof", conflicts_with = "proof")]
+        commitment : Option<PathBuf>,
+        #[arg(long)]
+        proof : Option<PathBuf>,
+    },
+    /// prove a new value for a member and update the aggregated commitment and opening key
+    Update {
+        #[arg(long)]
+        r1cs : PathBuf,
+        #[arg(long)]
+        wasm : PathBuf,
+        #[arg(long)]
+        pk : PathBuf,
+        #[arg(long)]
+        proof : PathBuf,
+        #[arg(long)]
+        aggregated_commitment : PathBuf,
+        #[arg(long)]
+        aggregated_opening_key : PathBuf,
+        /// one value per committed witness, repeat for range_vector proofs
+        #[arg(long, required = true)]
+        value : Vec<String>,
+        #[arg(long)]
+        reduce : bool,
+        #[arg(long)]
+        seed : Option<u64>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Kind {
+    Proof,
+    Vk,
+    Pk,
+    Commitment,
+    OpeningKey,
+}
+
+// json output and whether the check of verify / open passed
+type Output = (Value, bool);
+
+fn main() {
+    let cli = Cli::parse();
+    let result = match cli.curve {
+        Curve::Bn128 => run::<Bn254>(cli.command),
+        Curve::Bls12_381 => run::<Bls12_381>(cli.command),
+        Curve::Bls12_377 => run::<Bls12_377>(cli.command),
+    };
+
+    match result {
+        Ok((output, passed)) => {
+            println!("{}", output);
+            if !passed {
+                exit(1);
+            }
+        },
+        Err(e) => {
+            eprintln!("{}", json!({ "error" : e.to_string() }));
+            exit(2);
+        },
+    }
+}
+
+fn run<E:Pairing>(command : Command) -> Result<Output, Error> {
+    match command {
+        Command::Setup { r1cs, pk, vk, commit_witness_count, seed, link_label, link_generators, link_pk, link_vk } => {
+            let mut output = json!({
+                "pk" : path(&pk),
+                "vk" : path(&vk),
+                "commit_witness_count" : commit_witness_count,
+            });
+            let seed = setup_seed(seed, &mut output);
+            let generators = match (link_label, link_generators) {
+                (Some(label), _) => {
+                    output["link_label"] = json!(label);
+                    Some(LinkGenerators::<E>::Hashed(label.into_bytes()))
+                },
+                (None, Some(link_generators)) => {
+                    output["link_generators"] = json!(path(&link_generators));
+                    Some(LinkGenerators::Provided(read_link_generators_from_file::<E>(&path(&link_generators))?))
+                },
+                (None, None) => None,
+            };
+            match (generators, link_pk, link_vk) {
+                (Some(generators), Some(link_pk), Some(link_vk)) => {
+                    setup_from_circom_r1cs_with_link::<E>(
+                        &path(&r1cs),
+                        commit_witness_count,
+                        seed,
+                        &generators,
+                        &path(&pk),
+                        &path(&vk),
+                        &path(&link_pk),
+                        &path(&link_vk)
+                    )?;
+                    output["link_pk"] = json!(path(&link_pk));
+                    output["link_vk"] = json!(path(&link_vk));
+                    output["recorded_link_generators"] = json!(link_generators_path(&path(&link_pk)));
+                },
+                _ => {
+                    let (_, proving_key, _) = setup_from_circom_r1cs::<E>(&path(&r1cs), commit_witness_count, seed)?;
+                    write_to_file_compresed_proving_key::<E>(proving_key, &path(&pk), &path(&vk))?;
+                },
+            }
+            output["public_inputs_count"] = json!(public_inputs_count(&read_compressed::<VerifyingKey<E>>(&vk)?));
+            Ok((output, true))
+        },
+        Command::SetupRange { bits, value_count, pk, vk, seed } => {
+            let mut output = json!({
+                "pk" : path(&pk),
+                "vk" : path(&vk),
+                "bits" : bits,
+                "commit_witness_count" : value_count,
+            });
+            let seed = setup_seed(seed, &mut output);
+            setup_native_range::<E>(bits, value_count, seed, &path(&pk), &path(&vk))?;
+            Ok((output, true))
+        },
+        Command::ProveRange { pk, proof, bits, values, reduce, seed } => {
+            let values = values
+                .iter()
+                .map(|value| parse_field_element::<E::ScalarField>(value, reduce_mode(reduce)))
+                .collect::<Result<Vec<_>, _>>()?;
+            let opening_key = prove_native_range::<E>(&path(&pk), &path(&proof), bits, &values, seed_or_random(seed))?;
+            let opening_key_path = format!("{}_opening_key.json", path(&proof).trim_end_matches(".bin"));
+            std::fs::write(&opening_key_path, opening_key)?;
+            let commitment = Commitment::<E>::from_proof(&read_proof_from_file::<E>(&path(&proof))?);
+            Ok((json!({
+                "proof" : path(&proof),
+                "opening_key" : opening_key_path,
+                "commitment" : commitment.to_bytes().encode_hex::<String>(),
+            }), true))
+        },
+        Command::CheckLink { link_vk, label, generators } => {
+            let link_vk = read_compressed::<VerifyingKeyWithLink<E>>(&link_vk)?;
+            let valid = match (label, generators) {
+                (Some(label), _) => verify_link_generators::<E>(&link_vk, label.as_bytes()),
+                (None, Some(generators)) => verify_provided_link_generators::<E>(
+                    &link_vk,
+                    &read_link_generators_from_file::<E>(&path(&generators))?
+                ),
+                (None, None) => return Err(Error::Commitment("no label or generators".to_string())),
+            };
+            Ok((json!({ "valid" : valid }), valid))
+        },
+        Command::Prove { r1cs, wasm, pk, proof, inputs, commit_witness_count, opening_key, sym, signals, reduce, seed } => {
+            let inputs = parse_inputs::<E>(&inputs, reduce_mode(reduce))?;
+            let opening_key_path = match opening_key {
+                Some(opening_key) => path(&opening_key),
+                None => format!("{}_opening_key.json", path(&proof).trim_end_matches(".bin")),
+            };
+            match sym {
+                Some(sym) => {
+                    let opening_key = prove_signals::<E, _>(
+                        &path(&r1cs),
+                        &path(&pk),
+                        &path(&wasm),
+                        &path(&sym),
+                        &path(&proof),
+                        &signals,
+                        inputs,
+                        seed_or_random(seed)
+                    )?;
+                    std::fs::write(&opening_key_path, opening_key)?;
+                },
+                None => {
+                    let (created_proof, created_opening_key) = create_proof::<E, _>(
+                        &path(&r1cs),
+                        &path(&pk),
+                        &path(&wasm),
+                        commit_witness_count,
+                        inputs,
+                        seed_or_random(seed)
+                    )?;
+                    std::fs::write(path(&proof), proof_to_bytes(&created_proof))?;
+                    created_opening_key.write_to_file(&opening_key_path)?;
+                },
+            }
+            let commitment = Commitment::from_proof(&read_proof_from_file::<E>(&path(&proof))?);
+            Ok((json!({
+                "proof" : path(&proof),
+                "opening_key" : opening_key_path,
+                "commitment" : commitment.to_bytes().encode_hex::<String>(),
+            }), true))
+        },
+        Command::Verify { vk, proof, public_inputs, context } => {
+            let vk = read_compressed::<VerifyingKey<E>>(&vk)?;
+            let proof = read_proof_from_file::<E>(&path(&proof))?;
+            let mut public_inputs = public_inputs
+                .iter()
+                .map(|input| parse_field_element::<E::ScalarField>(input, ReduceMode::Strict))
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(context) = context {
+                public_inputs.push(context_to_field::<E::ScalarField>(context.as_bytes()));
+            }
+            let verified = verify_circuit_proof::<E>(&vk, &proof, &public_inputs);
+            Ok((json!({ "verified" : verified }), verified))
+        },
+        Command::Inspect { kind, file } => Ok((inspect::<E>(kind, &file)?, true)),
+        Command::Aggregate { proofs, out, opening_keys, opening_key_out } => {
+            let mut aggregated_commitment = Commitment::<E>::from_proof(&read_proof_from_file::<E>(&path(&proofs[0]))?);
+            for proof in proofs.iter().skip(1) {
+                aggregated_commitment = aggregated_commitment.add(&Commitment::from_proof(&read_proof_from_file::<E>(&path(proof))?));
+            }
+            aggregated_commitment.write_to_file(&path(&out))?;
+
+            let mut output = json!({
+                "aggregated_commitment" : path(&out),
+                "commitment" : aggregated_commitment.to_bytes().encode_hex::<String>(),
+                "proofs" : proofs.len(),
+            });
+            if let (Some(first), Some(opening_key_out)) = (opening_keys.first(), opening_key_out) {
+                let mut aggregated_opening_key = OpeningKey::<E>::read_from_file(&path(first))?;
+                for opening_key in opening_keys.iter().skip(1) {
+                    aggregated_opening_key = aggregated_opening_key.add(&OpeningKey::read_from_file(&path(opening_key))?)?;
+                }
+                aggregated_opening_key.write_to_file(&path(&opening_key_out))?;
+                output["aggregated_opening_key"] = json!(path(&opening_key_out));
+            }
+            Ok((output, true))
+        },
+        Command::Open { vk, opening_key, commitment, proof } => {
+            let vk = read_compressed::<VerifyingKey<E>>(&vk)?;
+            let opening_key = OpeningKey::<E>::read_from_file(&path(&opening_key))?;
+            let commitment = match (commitment, proof) {
+                (Some(commitment), _) => Commitment::<E>::read_from_file(&path(&commitment))?,
+                (None, Some(proof)) => Commitment::from_proof(&read_proof_from_file::<E>(&path(&proof))?),
+                (None, None) => return Err(Error::Commitment("no commitment or proof".to_string())),
+            };
+            let opens = opening_key.commit(&vk)? == commitment;
+            Ok((json!({ "opens" : opens }), opens))
+        },
+        Command::Update { r1cs, wasm, pk, proof, aggregated_commitment, aggregated_opening_key, value, reduce, seed } => {
+            update_aggregated_commitment::<E>(
+                &path(&r1cs),
+                &path(&pk),
+                &path(&wasm),
+                &path(&proof),
+                &path(&aggregated_commitment),
+                &path(&aggregated_opening_key),
+                value,
+                reduce_mode(reduce),
+                seed_or_random(seed)
+            )?;
+            let commitment = Commitment::<E>::read_from_file(&path(&aggregated_commitment))?;
+            Ok((json!({
+                "proof" : path(&proof),
+                "aggregated_commitment" : path(&aggregated_commitment),
+                "aggregated_opening_key" : path(&aggregated_opening_key),
+                "commitment" : commitment.to_bytes().encode_hex::<String>(),
+            }), true))
+        },
+    }
+}
+
+fn inspect<E:Pairing>(kind : Kind, file : &Path) -> Result<Value, Error> {
+    Ok(match kind {
+        Kind::Proof => {
+            let proof = read_compressed::<Proof<E>>(file)?;
+            json!({
+                "a" : to_hex(&proof.a),
+                "b" : to_hex(&proof.b),
+                "c" : to_hex(&proof.c),
+                "d" : to_hex(&proof.d),
+            })
+        },
+        Kind::Vk => vk_json(&read_compressed::<VerifyingKey<E>>(file)?),
+        Kind::Pk => vk_json(&read_compressed::<ProvingKey<E>>(file)?.vk),
+        Kind::Commitment => json!({
+            "commitment" : Commitment::<E>::read_from_file(&path(file))?.to_bytes().encode_hex::<String>(),
+        }),
+        Kind::OpeningKey => {
+            let opening_key = OpeningKey::<E>::read_from_file(&path(file))?;
+            json!({
+                "m" : opening_key.m.iter().map(field_element_to_hex).collect::<Vec<_>>(),
+                "v" : field_element_to_hex(&opening_key.v),
+            })
+        },
+    })
+}
+
+fn vk_json<E:Pairing>(vk : &VerifyingKey<E>) -> Value {
+    json!({
+        "commit_witness_count" : vk.commit_witness_count,
+        "public_inputs_count" : public_inputs_count(vk),
+        "pedersen_bases" : vk.gamma_abc_g1[1 + public_inputs_count(vk)..].iter().map(to_hex).collect::<Vec<_>>(),
+        "blinding_base" : to_hex(&vk.eta_gamma_inv_g1),
+    })
+}
+
+// gamma_abc_g1 : constant 1, public inputs, committed witnesses
+fn public_inputs_count<E:Pairing>(vk : &VerifyingKey<E>) -> usize {
+    vk.gamma_abc_g1.len() - 1 - vk.commit_witness_count
+}
+
+// name=value or name=value,value,...
+fn parse_inputs<E:Pairing>(
+    inputs : &[String],
+    mode : ReduceMode
+) -> Result<HashMap<String, Vec<E::ScalarField>>, Error> {
+    let mut parsed = HashMap::new();
+    for input in inputs {
+        let (name, values) = input
+            .split_once('=')
+            .ok_or_else(|| Error::Circuit(format!("expected name=value, got {}", input)))?;
+        let values = values
+            .split(',')
+            .map(|value| parse_field_element::<E::ScalarField>(value, mode))
+            .collect::<Result<Vec<_>, _>>()?;
+        parsed.insert(name.to_string(), values);
+    }
+    Ok(parsed)
+}
+
+fn read_compressed<T:CanonicalDeserialize>(file : &Path) -> Result<T, Error> {
+    Ok(T::deserialize_compressed(&*read(path(file))?)?)
+}
+
+fn to_hex<T:CanonicalSerialize>(value : &T) -> String {
+    let mut compressed_bytes:Vec<u8> = Vec::new();
+    value.serialize_compressed(&mut compressed_bytes).unwrap();
+    compressed_bytes.encode_hex::<String>()
+}
+
+// the library takes paths as str, relative ones are resolved against the working directory
+fn path(file : &Path) -> String {
+    file.to_string_lossy().to_string()
+}
+
+fn reduce_mode(reduce : bool) -> ReduceMode {
+    ReduceMode::from_flag(Some(reduce))
+}
+
+fn seed_or_random(seed : Option<u64>) -> u64 {
+    seed.unwrap_or_else(|| StdRng::from_entropy().gen())
+}
+
+// the setup trapdoor follows from the seed, only a seed given on the command line is echoed
+fn setup_seed(seed : Option<u64>, output : &mut Value) -> u64 {
+    if let Some(seed) = seed {
+        output["seed"] = json!(seed);
+        eprintln!("{}", json!({ "warning" : "seeded setup, anyone who knows the seed can forge proofs, use it for tests only" }));
+    }
+    seed_or_random(seed)
+}
@@ -0,0 +1,211 @@
+use ark_ec::pairing::Pairing;
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::{
+    alloc::AllocVar,
+    boolean::Boolean,
+    eq::EqGadget,
+    fields::fp::FpVar,
+};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use legogroth16::circom::{r1cs::R1CSFile, CircomCircuit};
+use num_bigint::BigUint;
+use std::io::Cursor;
+
+use crate::error::Error;
+
+// circuit usable with setup::setup_circuit and prover::prove_circuit
+// the first commit_witness_count witnesses it allocates (after the public inputs) are committed in proof.d
+pub trait CommittedCircuit<F:PrimeField> : ConstraintSynthesizer<F> {
+    fn commit_witness_count(&self) -> usize;
+
+    // values of the committed witnesses, None for a circuit without assignment (setup)
+    fn committed_witnesses(&self) -> Option<Vec<F>>;
+}
+
+// circom circuit with the number of committed witnesses, the circom signals after the public ones
+#[derive(Clone)]
+pub struct CircomCommittedCircuit<E:Pairing> {
+    pub circuit : CircomCircuit<E>,
+    pub commit_witness_count : usize,
+}
+
+// same as CircomCircuit::from_r1cs_file for the content of a .r1cs file
+pub fn circom_circuit_from_r1cs_bytes<E:Pairing>(r1cs_bytes : &[u8]) -> Result<CircomCircuit<E>, Error> {
+    Ok(CircomCircuit::setup(R1CSFile::<E>::new(Cursor::new(r1cs_bytes))?.into()))
+}
+
+impl<E:Pairing> ConstraintSynthesizer<E::ScalarField> for CircomCommittedCircuit<E> {
+    fn generate_constraints(self, cs : ConstraintSystemRef<E::ScalarField>) -> Result<(), SynthesisError> {
+        self.circuit.generate_constraints(cs)
+    }
+}
+
+impl<E:Pairing> CommittedCircuit<E::ScalarField> for CircomCommittedCircuit<E> {
+    fn commit_witness_count(&self) -> usize {
+        self.commit_witness_count
+    }
+
+    fn committed_witnesses(&self) -> Option<Vec<E::ScalarField>> {
+        let public_inputs = self.circuit.get_public_inputs()?;
+        Some(self.circuit
+            .wires
+            .clone()?
+            .into_iter()
+            .skip(1 + public_inputs.len())
+            .take(self.commit_witness_count)
+            .collect())
+    }
+}
+
+// native version of `circom/range/range_bits_<n>.circom` and `range_vector_<n>x<N>.circom`
+// `0 <= values[i] < 2^bit_width`  for every value, no wasm witness calculator needed
+//
+// the values are allocated before anything else, so they are the committed witnesses of proof.d
+// (commit_witness_count = values.len(), same opening key layout as the circom circuits)
+#[derive(Clone, Debug)]
+pub struct RangeCircuit<F:PrimeField> {
+    pub bit_width : usize,
+    // None for the setup, the constraints do not depend on the values
+    pub values : Vec<Option<F>>,
+}
+
+impl<F:PrimeField> RangeCircuit<F> {
+    pub fn new(bit_width : usize, values : &[F]) -> Result<Self, Error> {
+        check_bit_width::<F>(bit_width)?;
+        if values.is_empty() {
+            return Err(Error::InvalidRange("no values to prove".to_string()));
+        }
+        for value in values {
+            let value_int : BigUint = (*value).into();
+            if value_int.bits() as usize > bit_width {
+                return Err(Error::InvalidRange(format!("{} is not less than 2^{}", value_int, bit_width)));
+            }
+        }
+        Ok(Self {
+            bit_width,
+            values : values.iter().copied().map(Some).collect(),
+        })
+    }
+
+    // circuit shape for setup_circuit, value_count = commit_witness_count
+    pub fn for_setup(bit_width : usize, value_count : usize) -> Result<Self, Error> {
+        check_bit_width::<F>(bit_width)?;
+        if value_count == 0 {
+            return Err(Error::InvalidRange("no values to prove".to_string()));
+        }
+        Ok(Self {
+            bit_width,
+            values : vec![None; value_count],
+        })
+    }
+}
+
+impl<F:PrimeField> CommittedCircuit<F> for RangeCircuit<F> {
+    fn commit_witness_count(&self) -> usize {
+        self.values.len()
+    }
+
+    fn committed_witnesses(&self) -> Option<Vec<F>> {
+        self.values.iter().copied().collect()
+    }
+}
+
+impl<F:PrimeField> ConstraintSynthesizer<F> for RangeCircuit<F> {
+    fn generate_constraints(self, cs : ConstraintSystemRef<F>) -> Result<(), SynthesisError> {
+        // committed witnesses first
+        let values = self.values
+            .iter()
+            .map(|value| FpVar::new_witness(cs.clone(), || value.ok_or(SynthesisError::AssignmentMissing)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (value, value_var) in self.values.iter().zip(values.iter()) {
+            let bits = value.map(|value| value.into_bigint().to_bits_le());
+            let bit_vars = (0..self.bit_width)
+                .map(|i| Boolean::new_witness(cs.clone(), || {
+                    bits.as_ref().map(|bits| bits[i]).ok_or(SynthesisError::AssignmentMissing)
+                }))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // sum 2^i * bits[i] == value
+            Boolean::le_bits_to_fp_var(&bit_vars)?.enforce_equal(value_var)?;
+        }
+        Ok(())
+    }
+}
+
+// bit decomposition is only unique below the field order
+pub(crate) fn check_bit_width<F:PrimeField>(bit_width : usize) -> Result<(), Error> {
+    if bit_width == 0 || bit_width >= F::MODULUS_BIT_SIZE as usize {
+        return Err(Error::InvalidRange(format!(
+            "bit width must be in [1, {}), got {}",
+            F::MODULUS_BIT_SIZE,
+            bit_width
+        )));
+    }
+    Ok(())
+}
+
+// native 8 bit range circuit over bn254, the fixture of the proof and commitment tests
+#[cfg(test)]
+pub(crate) mod test_util {
+    use ark_bn254::{Bn254, Fr};
+    use legogroth16::{Proof, ProvingKey};
+    use super::RangeCircuit;
+    use crate::{commitment::OpeningKey, prover::prove_circuit, setup::setup_circuit};
+
+    pub(crate) fn range_pk(value_count : usize) -> ProvingKey<Bn254> {
+        setup_circuit::<Bn254, _>(RangeCircuit::<Fr>::for_setup(8, value_count).unwrap(), 0).unwrap()
+    }
+
+    pub(crate) fn prove_values(pk : &ProvingKey<Bn254>, values : &[u64], seed : u64) -> (Proof<Bn254>, OpeningKey<Bn254>) {
+        let values = values.iter().map(|value| Fr::from(*value)).collect::<Vec<_>>();
+        prove_circuit::<Bn254, _>(RangeCircuit::new(8, &values).unwrap(), pk, None, seed).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Bn254, Fr};
+    use ark_relations::r1cs::ConstraintSystem;
+    use legogroth16::{prepare_verifying_key, verify_proof};
+    use crate::{prover::prove_circuit, setup::setup_circuit};
+
+    fn is_satisfied(circuit : RangeCircuit<Fr>) -> bool {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        circuit.generate_constraints(cs.clone()).unwrap();
+        cs.is_satisfied().unwrap()
+    }
+
+    #[test]
+    fn rejects_out_of_range_values() {
+        assert!(RangeCircuit::<Fr>::new(8, &[Fr::from(255u64)]).is_ok());
+        assert!(matches!(RangeCircuit::<Fr>::new(8, &[Fr::from(256u64)]), Err(Error::InvalidRange(_))));
+        assert!(matches!(RangeCircuit::<Fr>::new(8, &[Fr::from(1u64), -Fr::from(1u64)]), Err(Error::InvalidRange(_))));
+        assert!(matches!(RangeCircuit::<Fr>::new(8, &[]), Err(Error::InvalidRange(_))));
+        assert!(matches!(RangeCircuit::<Fr>::new(0, &[Fr::from(0u64)]), Err(Error::InvalidRange(_))));
+        assert!(matches!(RangeCircuit::<Fr>::for_setup(254, 1), Err(Error::InvalidRange(_))));
+    }
+
+    // values that bypass new() must not satisfy the constraints
+    #[test]
+    fn constraints_reject_out_of_range_values() {
+        assert!(is_satisfied(RangeCircuit::new(8, &[Fr::from(0u64), Fr::from(255u64)]).unwrap()));
+        assert!(!is_satisfied(RangeCircuit { bit_width : 8, values : vec![Some(Fr::from(256u64))] }));
+        assert!(!is_satisfied(RangeCircuit { bit_width : 8, values : vec![Some(-Fr::from(1u64))] }));
+    }
+
+    #[test]
+    fn committed_witnesses_are_the_values() {
+        let pk = setup_circuit::<Bn254, _>(RangeCircuit::<Fr>::for_setup(16, 3).unwrap(), 0).unwrap();
+        let values = [Fr::from(1u64), Fr::from(500u64), Fr::from(65535u64)];
+        let (proof, opening_key) = prove_circuit::<Bn254, _>(RangeCircuit::new(16, &values).unwrap(), &pk, None, 1).unwrap();
+
+        assert!(verify_proof(&prepare_verifying_key(&pk.vk), &proof, &[]).is_ok());
+        assert_eq!(opening_key.m, values.to_vec());
+        assert!(matches!(
+            prove_circuit::<Bn254, _>(RangeCircuit::new(16, &values[..2]).unwrap(), &pk, None, 1),
+            Err(Error::Circuit(_))
+        ));
+    }
+}
@@ -0,0 +1,258 @@
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_serialize::{CanonicalSerialize, CanonicalDeserialize};
+use legogroth16::{Proof, VerifyingKey};
+use std::{
+    fmt,
+    fs::{read, write},
+};
+
+use crate::{
+    error::{Error, Result},
+    field::{field_element_to_hex, parse_hex_field_element, ReduceMode},
+    prover::calculate_pedersen_commitment,
+};
+
+// pedersen commitment g_0*m_0 + ... + h*v, same as proof.d
+// commitments of the same key are additively homomorphic
+pub struct Commitment<E:Pairing> {
+    pub point : E::G1Affine,
+}
+
+impl<E:Pairing> Commitment<E> {
+    pub fn new(point : E::G1Affine) -> Self {
+        Self { point }
+    }
+
+    pub fn from_proof(proof : &Proof<E>) -> Self {
+        Self::new(proof.d)
+    }
+
+    pub fn add(&self, other : &Self) -> Self {
+        Self::new((self.point + other.point).into())
+    }
+
+    pub fn sub(&self, other : &Self) -> Self {
+        Self::new((self.point.into_group() - other.point.into_group()).into())
+    }
+
+    pub fn neg(&self) -> Self {
+        Self::new((-self.point.into_group()).into_affine())
+    }
+
+    pub fn mul(&self, scalar : &E::ScalarField) -> Self {
+        Self::new((self.point * scalar).into())
+    }
+
+    pub fn to_bytes(self) -> Vec<u8> {
+        let mut compressed_bytes:Vec<u8> = Vec::new();
+        self.point.serialize_compressed(&mut compressed_bytes).unwrap();
+        compressed_bytes
+    }
+
+    pub fn from_bytes(bytes : &[u8]) -> Result<Self> {
+        Ok(Self::new(E::G1Affine::deserialize_compressed(bytes)?))
+    }
+
+    pub fn read_from_file(path : &str) -> Result<Self> {
+        Self::from_bytes(&read(path)?)
+    }
+
+    pub fn write_to_file(&self, path : &str) -> Result<()> {
+        write(path, self.to_bytes())?;
+        Ok(())
+    }
+}
+
+impl<E:Pairing> Clone for Commitment<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E:Pairing> Copy for Commitment<E> {}
+
+impl<E:Pairing> PartialEq for Commitment<E> {
+    fn eq(&self, other : &Self) -> bool {
+        self.point == other.point
+    }
+}
+
+impl<E:Pairing> Eq for Commitment<E> {}
+
+impl<E:Pairing> fmt::Debug for Commitment<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self.point)
+    }
+}
+
+// opening of a commitment, m is the committed witness vector, v is the randomness
+// operations mirror the ones of Commitment, e.g.
+//   commit(a.add(b)) == commit(a).add(commit(b))
+pub struct OpeningKey<E:Pairing> {
+    pub m : Vec<E::ScalarField>,
+    pub v : E::ScalarField,
+}
+
+impl<E:Pairing> OpeningKey<E> {
+    pub fn new(m : Vec<E::ScalarField>, v : E::ScalarField) -> Self {
+        Self { m, v }
+    }
+
+    pub fn add(&self, other : &Self) -> Result<Self> {
+        self.check_len(other)?;
+        Ok(Self::new(
+            self.m.iter().zip(&other.m).map(|(a, b)| *a + b).collect(),
+            self.v + other.v
+        ))
+    }
+
+    pub fn sub(&self, other : &Self) -> Result<Self> {
+        self.check_len(other)?;
+        Ok(Self::new(
+            self.m.iter().zip(&other.m).map(|(a, b)| *a - b).collect(),
+            self.v - other.v
+        ))
+    }
+
+    pub fn neg(&self) -> Self {
+        Self::new(self.m.iter().map(|m| -*m).collect(), -self.v)
+    }
+
+    pub fn mul(&self, scalar : &E::ScalarField) -> Self {
+        Self::new(self.m.iter().map(|m| *m * scalar).collect(), self.v * scalar)
+    }
+
+    pub fn commit(&self, vk : &VerifyingKey<E>) -> Result<Commitment<E>> {
+        Ok(Commitment::new(calculate_pedersen_commitment::<E>(vk, &self.m, self.v)?))
+    }
+
+    // {"m" : hex, "v" : hex} for a single committed witness
+    // {"m" : [hex, ...], "v" : hex} for a vector commitment
+    pub fn to_json(&self) -> String {
+        let m = match self.m.as_slice() {
+            [m] => serde_json::json!(field_element_to_hex(m)),
+            m => serde_json::json!(m.iter().map(field_element_to_hex).collect::<Vec<_>>()),
+        };
+        serde_json::to_string(&serde_json::json!({
+            "m" : m,
+            "v" : field_element_to_hex(&self.v)
+        })).unwrap()
+    }
+
+    pub fn from_json(json : &str) -> Result<Self> {
+        let opening_key_json : serde_json::Value = serde_json::from_str(json)
+            .map_err(|e| Error::Commitment(e.to_string()))?;
+
+        let m = match &opening_key_json["m"] {
+            serde_json::Value::Array(m) => m.iter().map(hex_value::<E>).collect::<Result<Vec<_>>>()?,
+            m => vec![hex_value::<E>(m)?],
+        };
+        let v = hex_value::<E>(&opening_key_json["v"])?;
+        Ok(Self::new(m, v))
+    }
+
+    pub fn read_from_file(path : &str) -> Result<Self> {
+        let json = read(path)?;
+        Self::from_json(&String::from_utf8_lossy(&json))
+    }
+
+    pub fn write_to_file(&self, path : &str) -> Result<()> {
+        write(path, self.to_json())?;
+        Ok(())
+    }
+
+    fn check_len(&self, other : &Self) -> Result<()> {
+        if self.m.len() != other.m.len() {
+            return Err(Error::Commitment(format!(
+                "opening keys have {} and {} committed witnesses",
+                self.m.len(),
+                other.m.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn hex_value<E:Pairing>(value : &serde_json::Value) -> Result<E::ScalarField> {
+    let hex_string = value
+        .as_str()
+        .ok_or_else(|| Error::Commitment(format!("expected hex string, got {}", value)))?;
+    Ok(parse_hex_field_element::<E::ScalarField>(hex_string, ReduceMode::Strict)?)
+}
+
+impl<E:Pairing> Clone for OpeningKey<E> {
+    fn clone(&self) -> Self {
+        Self::new(self.m.clone(), self.v)
+    }
+}
+
+impl<E:Pairing> PartialEq for OpeningKey<E> {
+    fn eq(&self, other : &Self) -> bool {
+        self.m == other.m && self.v == other.v
+    }
+}
+
+impl<E:Pairing> Eq for OpeningKey<E> {}
+
+impl<E:Pairing> fmt::Debug for OpeningKey<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpeningKey")
+            .field("m", &self.m.iter().map(|m| m.to_string()).collect::<Vec<_>>())
+            .field("v", &self.v.to_string())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Bn254, Fr};
+    use crate::circuit::test_util::{prove_values, range_pk};
+
+    fn opening_key(m : &[u64], v : u64) -> OpeningKey<Bn254> {
+        OpeningKey::new(m.iter().map(|m| Fr::from(*m)).collect(), Fr::from(v))
+    }
+
+    #[test]
+    fn arithmetic_matches_opening_keys() {
+        let vk = range_pk(2).vk;
+        let a = opening_key(&[3, 5], 11);
+        let b = opening_key(&[7, 2], 13);
+        let scalar = Fr::from(9u64);
+
+        assert_eq!(a.add(&b).unwrap().commit(&vk).unwrap(), a.commit(&vk).unwrap().add(&b.commit(&vk).unwrap()));
+        assert_eq!(a.sub(&b).unwrap().commit(&vk).unwrap(), a.commit(&vk).unwrap().sub(&b.commit(&vk).unwrap()));
+        assert_eq!(a.neg().commit(&vk).unwrap(), a.commit(&vk).unwrap().neg());
+        assert_eq!(a.mul(&scalar).commit(&vk).unwrap(), a.commit(&vk).unwrap().mul(&scalar));
+    }
+
+    #[test]
+    fn proof_commitment_opens_with_its_key() {
+        let pk = range_pk(1);
+        let (proof, key) = prove_values(&pk, &[42], 2);
+
+        assert_eq!(key.commit(&pk.vk).unwrap(), Commitment::from_proof(&proof));
+        assert_ne!(opening_key(&[43], 0).add(&key).unwrap().commit(&pk.vk).unwrap(), Commitment::from_proof(&proof));
+    }
+
+    #[test]
+    fn round_trips() {
+        for key in [opening_key(&[42], 7), opening_key(&[1, 2, 3], 7)] {
+            assert_eq!(OpeningKey::<Bn254>::from_json(&key.to_json()).unwrap(), key);
+        }
+
+        let vk = range_pk(1).vk;
+        let commitment = opening_key(&[42], 7).commit(&vk).unwrap();
+        assert_eq!(Commitment::<Bn254>::from_bytes(&commitment.to_bytes()).unwrap(), commitment);
+    }
+
+    #[test]
+    fn rejects_mismatched_lengths() {
+        let vk = range_pk(2).vk;
+
+        assert!(matches!(opening_key(&[1], 0).add(&opening_key(&[1, 2], 0)), Err(Error::Commitment(_))));
+        assert!(matches!(opening_key(&[1], 0).commit(&vk), Err(Error::Commitment(_))));
+        assert!(matches!(opening_key(&[1, 2, 3], 0).commit(&vk), Err(Error::Commitment(_))));
+        assert!(OpeningKey::<Bn254>::from_json(r#"{"m" : 42, "v" : "0x07"}"#).is_err());
+    }
+}
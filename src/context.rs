@@ -0,0 +1,41 @@
+use ark_ff::PrimeField;
+use blake2::Blake2b512;
+use dock_crypto_utils::hashing_utils::field_elem_from_try_and_incr;
+
+// name of the public circuit input holding the context hash, see circom/range/range_context_64.circom
+pub const CONTEXT_INPUT : &str = "context";
+
+const CONTEXT_DOMAIN : &[u8] = b"napirs-legogroth16 context";
+
+// binds a proof to an application context (session id, account id, epoch, ...)
+// the context bytes are hashed to a field element, the circuit takes it as a public input
+// so a proof made for one context does not verify for another
+pub fn context_to_field<F:PrimeField>(
+    context : &[u8]
+) -> F {
+    field_elem_from_try_and_incr::<F, Blake2b512>(&[CONTEXT_DOMAIN, context].concat())
+}
+
+// circuit input for prover::prove
+pub fn context_input<F:PrimeField>(
+    context : &[u8]
+) -> (String, Vec<F>) {
+    (CONTEXT_INPUT.to_string(), vec![context_to_field::<F>(context)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+
+    #[test]
+    fn context_hash_is_deterministic_and_separates_contexts() {
+        assert_eq!(context_to_field::<Fr>(b"session 1"), context_to_field::<Fr>(b"session 1"));
+        assert_ne!(context_to_field::<Fr>(b"session 1"), context_to_field::<Fr>(b"session 2"));
+        assert_ne!(context_to_field::<Fr>(b""), context_to_field::<Fr>(b"\0"));
+        assert_eq!(
+            context_input::<Fr>(b"session 1"),
+            ("context".to_string(), vec![context_to_field::<Fr>(b"session 1")])
+        );
+    }
+}
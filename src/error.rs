@@ -0,0 +1,78 @@
+use ark_serialize::SerializationError;
+use legogroth16::{aggregation::error::AggregationError, circom::CircomError};
+use std::{fmt, io};
+
+use crate::{field::FieldParseError, inputs::InputMismatch};
+
+#[derive(Debug)]
+pub enum Error {
+    Field(FieldParseError),
+    InvalidRange(String),
+    Commitment(String),
+    Serialization(SerializationError),
+    Io(io::Error),
+    Transaction(String),
+    Ledger(String),
+    Verification(String),
+    Aggregation(String),
+    Circuit(String),
+    Inputs(InputMismatch),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Field(e) => write!(f, "{}", e),
+            Error::InvalidRange(s) => write!(f, "invalid range : {}", s),
+            Error::Commitment(s) => write!(f, "invalid commitment : {}", s),
+            Error::Serialization(e) => write!(f, "serialization error : {}", e),
+            Error::Io(e) => write!(f, "io error : {}", e),
+            Error::Transaction(s) => write!(f, "transaction error : {}", s),
+            Error::Ledger(s) => write!(f, "ledger error : {}", s),
+            Error::Verification(s) => write!(f, "verification failed : {}", s),
+            Error::Aggregation(s) => write!(f, "aggregation error : {}", s),
+            Error::Circuit(s) => write!(f, "circuit error : {}", s),
+            Error::Inputs(e) => write!(f, "invalid circuit inputs : {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<FieldParseError> for Error {
+    fn from(e : FieldParseError) -> Self {
+        Error::Field(e)
+    }
+}
+
+impl From<SerializationError> for Error {
+    fn from(e : SerializationError) -> Self {
+        Error::Serialization(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e : io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<AggregationError> for Error {
+    fn from(e : AggregationError) -> Self {
+        Error::Aggregation(format!("{:?}", e))
+    }
+}
+
+impl From<legogroth16::error::Error> for Error {
+    fn from(e : legogroth16::error::Error) -> Self {
+        Error::Circuit(format!("{:?}", e))
+    }
+}
+
+impl From<CircomError> for Error {
+    fn from(e : CircomError) -> Self {
+        Error::Circuit(format!("{:?}", e))
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,216 @@
+use ark_ff::{PrimeField, BigInteger};
+use num_bigint::{BigInt, BigUint, Sign};
+use hex::ToHex;
+use std::fmt;
+
+// how to treat values outside of [0, p)
+// Strict : reject (default)
+// Reduce : reduce mod p
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ReduceMode {
+    #[default]
+    Strict,
+    Reduce,
+}
+
+impl ReduceMode {
+    pub fn from_flag(reduce : Option<bool>) -> Self {
+        match reduce {
+            Some(true) => ReduceMode::Reduce,
+            _ => ReduceMode::Strict
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endianness {
+    Big,
+    Little,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FieldParseError {
+    Empty,
+    InvalidDigits(String),
+    NonCanonical(String),
+}
+
+impl fmt::Display for FieldParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldParseError::Empty => write!(f, "empty field element"),
+            FieldParseError::InvalidDigits(s) => write!(f, "invalid field element : {}", s),
+            FieldParseError::NonCanonical(s) => write!(f, "field element is not less than the field order : {}", s),
+        }
+    }
+}
+
+impl std::error::Error for FieldParseError {}
+
+// accepted string formats
+//  decimal : "1234", "-1234"
+//  hex     : "0x04d2", "-0x04d2"
+// negative values are mapped to p - |value|
+pub fn parse_field_element<F:PrimeField>(
+    input : &str,
+    mode : ReduceMode
+) -> Result<F, FieldParseError> {
+    let input = input.trim();
+    let (negative, digits) = match input.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, input.strip_prefix('+').unwrap_or(input)),
+    };
+
+    let magnitude = match digits.strip_prefix("0x").or_else(|| digits.strip_prefix("0X")) {
+        Some(hex_digits) => parse_radix(hex_digits, 16, input)?,
+        None => parse_radix(digits, 10, input)?,
+    };
+
+    signed_to_field::<F>(negative, magnitude, mode, input)
+}
+
+// hex string without sign, "0x" prefix is optional
+// this is the format used by the opening key json files
+pub fn parse_hex_field_element<F:PrimeField>(
+    hex_string : &str,
+    mode : ReduceMode
+) -> Result<F, FieldParseError> {
+    let hex_string = hex_string.trim();
+    let digits = hex_string.strip_prefix("0x").unwrap_or(hex_string);
+    let magnitude = parse_radix(digits, 16, hex_string)?;
+
+    signed_to_field::<F>(false, magnitude, mode, hex_string)
+}
+
+pub fn field_element_from_bytes<F:PrimeField>(
+    bytes : &[u8],
+    endianness : Endianness,
+    mode : ReduceMode
+) -> Result<F, FieldParseError> {
+    if bytes.is_empty() {
+        return Err(FieldParseError::Empty);
+    }
+    let magnitude = match endianness {
+        Endianness::Big => BigUint::from_bytes_be(bytes),
+        Endianness::Little => BigUint::from_bytes_le(bytes),
+    };
+    let repr = bytes.encode_hex::<String>();
+
+    signed_to_field::<F>(false, magnitude, mode, &repr)
+}
+
+// canonical big-endian hex, same as the opening key json encoding
+pub fn field_element_to_hex<F:PrimeField>(
+    element : &F
+) -> String {
+    element.into_bigint().to_bytes_be().encode_hex::<String>()
+}
+
+pub fn field_element_to_decimal<F:PrimeField>(
+    element : &F
+) -> String {
+    let value : BigUint = (*element).into();
+    value.to_str_radix(10)
+}
+
+fn parse_radix(
+    digits : &str,
+    radix : u32,
+    input : &str
+) -> Result<BigUint, FieldParseError> {
+    if digits.is_empty() {
+        return Err(FieldParseError::Empty);
+    }
+    BigUint::parse_bytes(digits.as_bytes(), radix)
+        .ok_or_else(|| FieldParseError::InvalidDigits(input.to_string()))
+}
+
+fn signed_to_field<F:PrimeField>(
+    negative : bool,
+    magnitude : BigUint,
+    mode : ReduceMode,
+    input : &str
+) -> Result<F, FieldParseError> {
+    let modulus : BigUint = F::MODULUS.into();
+    if mode == ReduceMode::Strict && magnitude >= modulus {
+        return Err(FieldParseError::NonCanonical(input.to_string()));
+    }
+
+    let sign = if negative { Sign::Minus } else { Sign::Plus };
+    let value = BigInt::from_biguint(sign, magnitude);
+    let modulus = BigInt::from_biguint(Sign::Plus, modulus);
+    let reduced = ((value % &modulus) + &modulus) % &modulus;
+
+    Ok(F::from(reduced.to_biguint().unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+
+    fn modulus() -> BigUint {
+        Fr::MODULUS.into()
+    }
+
+    #[test]
+    fn parses_decimal_hex_and_negative() {
+        assert_eq!(parse_field_element::<Fr>("1234", ReduceMode::Strict), Ok(Fr::from(1234u64)));
+        assert_eq!(parse_field_element::<Fr>("0x04d2", ReduceMode::Strict), Ok(Fr::from(1234u64)));
+        assert_eq!(parse_field_element::<Fr>("-1", ReduceMode::Strict), Ok(-Fr::from(1u64)));
+        assert_eq!(parse_field_element::<Fr>("-0x10", ReduceMode::Strict), Ok(-Fr::from(16u64)));
+    }
+
+    #[test]
+    fn parses_more_than_64_bits() {
+        let value = parse_field_element::<Fr>("18446744073709551616", ReduceMode::Strict).unwrap();
+        assert_eq!(value, Fr::from(u64::MAX) + Fr::from(1u64));
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert_eq!(parse_field_element::<Fr>("", ReduceMode::Strict), Err(FieldParseError::Empty));
+        assert_eq!(parse_field_element::<Fr>("0x", ReduceMode::Strict), Err(FieldParseError::Empty));
+        assert!(matches!(parse_field_element::<Fr>("12a", ReduceMode::Strict), Err(FieldParseError::InvalidDigits(_))));
+        assert!(matches!(parse_field_element::<Fr>("0xzz", ReduceMode::Strict), Err(FieldParseError::InvalidDigits(_))));
+    }
+
+    #[test]
+    fn rejects_non_canonical_unless_reduced() {
+        let p_plus_one = (modulus() + 1u32).to_str_radix(10);
+        assert!(matches!(
+            parse_field_element::<Fr>(&p_plus_one, ReduceMode::Strict),
+            Err(FieldParseError::NonCanonical(_))
+        ));
+        assert_eq!(parse_field_element::<Fr>(&p_plus_one, ReduceMode::Reduce), Ok(Fr::from(1u64)));
+    }
+
+    #[test]
+    fn hex_field_element_is_hex_without_prefix() {
+        assert_eq!(parse_hex_field_element::<Fr>("10", ReduceMode::Strict), Ok(Fr::from(16u64)));
+        assert_eq!(parse_hex_field_element::<Fr>("0x10", ReduceMode::Strict), Ok(Fr::from(16u64)));
+        assert!(parse_hex_field_element::<Fr>("0x0x10", ReduceMode::Strict).is_err());
+        let p = modulus().to_str_radix(16);
+        assert!(matches!(
+            parse_hex_field_element::<Fr>(&p, ReduceMode::Strict),
+            Err(FieldParseError::NonCanonical(_))
+        ));
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        let value = -Fr::from(42u64);
+        assert_eq!(parse_hex_field_element::<Fr>(&field_element_to_hex(&value), ReduceMode::Strict), Ok(value));
+    }
+
+    #[test]
+    fn bytes_big_and_little_endian() {
+        assert_eq!(field_element_from_bytes::<Fr>(&[0x01, 0x00], Endianness::Big, ReduceMode::Strict), Ok(Fr::from(256u64)));
+        assert_eq!(field_element_from_bytes::<Fr>(&[0x01, 0x00], Endianness::Little, ReduceMode::Strict), Ok(Fr::from(1u64)));
+        assert_eq!(field_element_from_bytes::<Fr>(&[], Endianness::Big, ReduceMode::Strict), Err(FieldParseError::Empty));
+        assert!(matches!(
+            field_element_from_bytes::<Fr>(&[0xff; 32], Endianness::Big, ReduceMode::Strict),
+            Err(FieldParseError::NonCanonical(_))
+        ));
+    }
+}
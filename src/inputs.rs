@@ -0,0 +1,229 @@
+use ark_ec::pairing::Pairing;
+use legogroth16::circom::WitnessCalculator;
+use std::fmt;
+
+use crate::{
+    error::{Error, Result},
+    sym::SymbolTable,
+};
+
+// provided inputs that do not match the input signals of the circuit
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InputMismatch {
+    // (signal, size), named only when the .sym file is given
+    pub missing : Vec<(String, usize)>,
+    // input values still missing that could not be named (no .sym file)
+    pub missing_values : usize,
+    pub unexpected : Vec<String>,
+    // (signal, expected size, provided size)
+    pub wrong_size : Vec<(String, usize, usize)>,
+}
+
+impl InputMismatch {
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.missing_values == 0 && self.unexpected.is_empty() && self.wrong_size.is_empty()
+    }
+}
+
+impl fmt::Display for InputMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if !self.missing.is_empty() {
+            parts.push(format!(
+                "missing signals : {}",
+                self.missing.iter().map(|(name, size)| format!("{} (size {})", name, size)).collect::<Vec<_>>().join(", ")
+            ));
+        }
+        if self.missing_values > 0 {
+            parts.push(format!(
+                "{} input values missing, pass the .sym file to name the signals",
+                self.missing_values
+            ));
+        }
+        if !self.unexpected.is_empty() {
+            parts.push(format!("unexpected signals : {}", self.unexpected.join(", ")));
+        }
+        if !self.wrong_size.is_empty() {
+            parts.push(format!(
+                "wrong size : {}",
+                self.wrong_size
+                    .iter()
+                    .map(|(name, expected, provided)| format!("{} (expected {}, got {})", name, expected, provided))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        write!(f, "{}", parts.join("; "))
+    }
+}
+
+impl std::error::Error for InputMismatch {}
+
+// checks the inputs against the input signals of the witness wasm before calculate_witnesses,
+// which would fail with an opaque wasm error.
+// the wasm can only be asked for the size of a named signal, so the missing signals are named
+// with the .sym file, without it only the number of missing values is known
+pub fn check_inputs<E:Pairing>(
+    calculator : &WitnessCalculator<E>,
+    sym : Option<&SymbolTable>,
+    inputs : &[(String, Vec<E::ScalarField>)]
+) -> Result<()> {
+    check_inputs_with(
+        |name| input_size(calculator, name),
+        || Ok(calculator.instance.get_input_count()? as usize),
+        sym,
+        inputs
+    )
+}
+
+// check_inputs with the wasm queries as closures :
+// input_size gives the size of an input signal (None if there is none), input_count the number of input values
+fn check_inputs_with<F>(
+    input_size : impl Fn(&str) -> Result<Option<usize>>,
+    input_count : impl FnOnce() -> Result<usize>,
+    sym : Option<&SymbolTable>,
+    inputs : &[(String, Vec<F>)]
+) -> Result<()> {
+    let mut mismatch = InputMismatch::default();
+
+    let mut provided_values = 0;
+    for (name, values) in inputs {
+        match input_size(name)? {
+            None => mismatch.unexpected.push(name.clone()),
+            Some(size) => {
+                provided_values += size;
+                if size != values.len() {
+                    mismatch.wrong_size.push((name.clone(), size, values.len()));
+                }
+            },
+        }
+    }
+
+    match sym {
+        Some(sym) => {
+            for name in sym.main_signals() {
+                if inputs.iter().any(|(input, _)| *input == name) {
+                    continue;
+                }
+                if let Some(size) = input_size(&name)? {
+                    mismatch.missing.push((name, size));
+                }
+            }
+        },
+        None => {
+            mismatch.missing_values = input_count()?.saturating_sub(provided_values);
+        },
+    }
+
+    if mismatch.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Inputs(mismatch))
+    }
+}
+
+// None if the circuit has no such input signal (the wasm returns 0 or -1)
+fn input_size<E:Pairing>(
+    calculator : &WitnessCalculator<E>,
+    name : &str
+) -> Result<Option<usize>> {
+    let (msb, lsb) = fnv(name);
+    let size = calculator.instance.get_signal_count(msb, lsb)? as i32;
+    Ok(if size > 0 { Some(size as usize) } else { None })
+}
+
+// signal name hash of the circom witness calculator (64 bit FNV-1a)
+fn fnv(name : &str) -> (u32, u32) {
+    let hash = name
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3));
+    ((hash >> 32) as u32, hash as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hash::Hasher;
+
+    // main.value[2] and main.limit are inputs, main.out is not
+    const SYM : &str = "1,1,0,main.out\n2,2,0,main.limit\n3,3,0,main.value[0]\n4,4,0,main.value[1]\n";
+
+    fn circuit_input_size(name : &str) -> Result<Option<usize>> {
+        Ok(match name {
+            "value" => Some(2),
+            "limit" => Some(1),
+            _ => None,
+        })
+    }
+
+    fn mismatch(sym : Option<&SymbolTable>, inputs : &[(String, Vec<u64>)]) -> InputMismatch {
+        match check_inputs_with(circuit_input_size, || Ok(3), sym, inputs) {
+            Err(Error::Inputs(mismatch)) => mismatch,
+            other => panic!("expected an input mismatch, got {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn fnv_matches_the_witness_calculator() {
+        for name in ["main.value", "value", ""] {
+            let mut hasher = fnv::FnvHasher::default();
+            hasher.write(name.as_bytes());
+            let hash = hasher.finish();
+            assert_eq!(fnv(name), ((hash >> 32) as u32, hash as u32));
+        }
+    }
+
+    #[test]
+    fn matching_inputs_pass() {
+        let sym = SymbolTable::parse(SYM).unwrap();
+        let inputs = vec![("value".to_string(), vec![1u64, 2]), ("limit".to_string(), vec![3])];
+        assert!(check_inputs_with(circuit_input_size, || Ok(3), Some(&sym), &inputs).is_ok());
+        assert!(check_inputs_with(circuit_input_size, || Ok(3), None, &inputs).is_ok());
+    }
+
+    #[test]
+    fn missing_signals_are_named_with_the_sym_file() {
+        let sym = SymbolTable::parse(SYM).unwrap();
+        let inputs = vec![("value".to_string(), vec![1u64, 2])];
+        let with_sym = mismatch(Some(&sym), &inputs);
+        assert_eq!(with_sym.missing, vec![("limit".to_string(), 1)]);
+        assert_eq!(with_sym.to_string(), "missing signals : limit (size 1)");
+
+        let without_sym = mismatch(None, &inputs);
+        assert_eq!(without_sym.missing_values, 1);
+        assert_eq!(without_sym.to_string(), "1 input values missing, pass the .sym file to name the signals");
+    }
+
+    // circom/bn128/range_proof.wasm has one input signal, value
+    #[test]
+    fn range_proof_wasm_inputs() {
+        use ark_bn254::{Bn254, Fr};
+
+        let calculator = WitnessCalculator::<Bn254>::from_wasm_file("circom/bn128/range_proof.wasm").unwrap();
+        let sym = SymbolTable::read_from_file("circom/bn128/range_proof.sym").unwrap();
+        let check = |sym : Option<&SymbolTable>, name : &str, values : &[u64]| {
+            let inputs = [(name.to_string(), values.iter().map(|value| Fr::from(*value)).collect())];
+            check_inputs::<Bn254>(&calculator, sym, &inputs).map_err(|e| e.to_string())
+        };
+
+        assert!(check(None, "value", &[42]).is_ok());
+        assert!(check(Some(&sym), "value", &[42]).is_ok());
+        assert!(check(None, "valu", &[42]).unwrap_err().contains("unexpected signals : valu"));
+        assert!(check(Some(&sym), "valu", &[42]).unwrap_err().contains("missing signals : value (size 1)"));
+        assert!(check(None, "value", &[42, 43]).unwrap_err().contains("wrong size : value (expected 1, got 2)"));
+    }
+
+    #[test]
+    fn unexpected_and_mis_sized_inputs() {
+        let inputs = vec![
+            ("value".to_string(), vec![1u64]),
+            ("limit".to_string(), vec![3]),
+            ("total".to_string(), vec![4]),
+        ];
+        let mismatch = mismatch(None, &inputs);
+        assert_eq!(mismatch.unexpected, vec!["total".to_string()]);
+        assert_eq!(mismatch.wrong_size, vec![("value".to_string(), 2, 1)]);
+        assert_eq!(mismatch.missing_values, 0);
+        assert_eq!(mismatch.to_string(), "unexpected signals : total; wrong size : value (expected 2, got 1)");
+    }
+}
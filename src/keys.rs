@@ -3,28 +3,33 @@ use ark_serialize::{
     CanonicalSerialize, 
     CanonicalDeserialize  
 };
-use legogroth16::{ProvingKey};
-use std::{
-    fs::{write, read},
-    io::Result,
-    path::PathBuf,
-};
+use legogroth16::{LinkPublicGenerators, ProvingKey, ProvingKeyWithLink, VerifyingKey, VerifyingKeyWithLink};
+use std::fs::{write, read};
 use ark_bn254::Bn254;
 use ark_bls12_381::Bls12_381;
+use ark_bls12_377::Bls12_377;
+
+use crate::error::Result;
 
 
 pub fn read_bn128_proving_key_from_file(
     path : &str
-) -> ProvingKey<Bn254> {
+) -> Result<ProvingKey<Bn254>> {
     read_compressed_proving_key_from_file::<Bn254>(path)
 }
 
 pub fn read_bls12_381_proving_key_from_file (
     path : &str
-) -> ProvingKey<Bls12_381> {
+) -> Result<ProvingKey<Bls12_381>> {
     read_compressed_proving_key_from_file::<Bls12_381>(path)
 }
 
+pub fn read_bls12_377_proving_key_from_file (
+    path : &str
+) -> Result<ProvingKey<Bls12_377>> {
+    read_compressed_proving_key_from_file::<Bls12_377>(path)
+}
+
 pub fn write_bn128_proving_key(
     proving_key : ProvingKey<Bn254>,
     pk_path : &str,
@@ -41,34 +46,117 @@ pub fn write_bls12_381_proving_key (
     write_to_file_compresed_proving_key::<Bls12_381>(proving_key, pk_path, vk_path)
 }
 
-fn write_to_file_compresed_proving_key<E:Pairing>(
+pub fn write_bls12_377_proving_key (
+    proving_key : ProvingKey<Bls12_377>,
+    pk_path : &str,
+    vk_path : &str
+) -> Result<()> {
+    write_to_file_compresed_proving_key::<Bls12_377>(proving_key, pk_path, vk_path)
+}
+
+pub fn write_to_file_compresed_proving_key<E:Pairing>(
     proving_key : ProvingKey<E>,
     pk_path : &str,
     vk_path : &str
+) -> Result<()> {
+    let (pk_bytes, vk_bytes) = compressed_proving_key_bytes::<E>(&proving_key)?;
+    write(pk_path, pk_bytes)?;
+    write(vk_path, vk_bytes)?;
+    Ok(())
+}
+
+// (pk, vk) : the content of the pk and vk files
+pub fn compressed_proving_key_bytes<E:Pairing>(
+    proving_key : &ProvingKey<E>
+) -> Result<(Vec<u8>, Vec<u8>)> {
+    let mut pk_bytes:Vec<u8> = Vec::new();
+    proving_key.serialize_compressed(&mut pk_bytes)?;
+
+    let mut vk_bytes:Vec<u8> = Vec::new();
+    proving_key.vk.serialize_compressed(&mut vk_bytes)?;
+
+    Ok((pk_bytes, vk_bytes))
+}
+
+pub fn read_compressed_proving_key_from_file<E:Pairing>(
+    path : &str
+) -> Result<ProvingKey<E>> {
+    proving_key_from_bytes::<E>(&read(path)?)
+}
+
+pub fn proving_key_from_bytes<E:Pairing>(
+    bytes : &[u8]
+) -> Result<ProvingKey<E>> {
+    Ok(ProvingKey::<E>::deserialize_compressed(bytes)?)
+}
+
+pub fn read_verifying_key_from_file<E:Pairing>(
+    path : &str
+) -> Result<VerifyingKey<E>> {
+    let readed_verifying_key_file:Vec<u8> = read(path)?;
+
+    Ok(VerifyingKey::<E>::deserialize_compressed(&*readed_verifying_key_file)?)
+}
+
+// link proving key and link verifying key (with the link generators),
+// the link generators alone go next to the link pk (link_generators_path)
+pub fn write_link_keys<E:Pairing>(
+    proving_key : &ProvingKeyWithLink<E>,
+    link_pk_path : &str,
+    link_vk_path : &str
 ) -> Result<()> {
     let mut compressed_bytes:Vec<u8> = Vec::new();
-    proving_key.serialize_compressed(&mut compressed_bytes).unwrap();
-    write(
-        abs_path(pk_path),
-        &compressed_bytes
-    ).unwrap();
+    proving_key.serialize_compressed(&mut compressed_bytes)?;
+    write(link_pk_path, &compressed_bytes)?;
 
     compressed_bytes.clear();
-    proving_key.vk.serialize_compressed(&mut compressed_bytes).unwrap();
-    write(
-        abs_path(vk_path),
-        &compressed_bytes
-    ).unwrap();
+    proving_key.vk.serialize_compressed(&mut compressed_bytes)?;
+    write(link_vk_path, &compressed_bytes)?;
+
+    write_link_generators::<E>(&link_generators_of(&proving_key.vk), link_generators_path(link_pk_path).as_str())
+}
 
+// link_pk.bin -> link_pk_generators.bin
+pub fn link_generators_path(link_pk_path : &str) -> String {
+    format!("{}{}", link_pk_path.trim_end_matches(".bin"), "_generators.bin")
+}
+
+// the link generators recorded in a link verifying key
+pub fn link_generators_of<E:Pairing>(
+    link_vk : &VerifyingKeyWithLink<E>
+) -> LinkPublicGenerators<E> {
+    LinkPublicGenerators {
+        pedersen_gens : link_vk.link_bases.clone(),
+        g1 : link_vk.link_pp.g1,
+        g2 : link_vk.link_pp.g2,
+    }
+}
+
+// readable by setup::read_link_generators_from_file
+pub fn write_link_generators<E:Pairing>(
+    link_generators : &LinkPublicGenerators<E>,
+    path : &str
+) -> Result<()> {
+    let mut compressed_bytes:Vec<u8> = Vec::new();
+    link_generators.serialize_compressed(&mut compressed_bytes)?;
+    write(path, &compressed_bytes)?;
     Ok(())
 }
 
-pub fn read_compressed_proving_key_from_file<E:Pairing>(
+pub fn read_link_proving_key_from_file<E:Pairing>(
+    path : &str
+) -> Result<ProvingKeyWithLink<E>> {
+    let readed_proving_key_file:Vec<u8> = read(path)?;
+
+    Ok(ProvingKeyWithLink::<E>::deserialize_compressed(&*readed_proving_key_file)?)
+}
+
+pub fn read_link_verifying_key_from_file<E:Pairing>(
     path : &str
-) -> ProvingKey<E> {
-    let readed_proving_key_file:Vec<u8> = read(abs_path(path)).unwrap();
+) -> Result<VerifyingKeyWithLink<E>> {
+    let readed_verifying_key_file:Vec<u8> = read(path)?;
 
-    ProvingKey::<E>::deserialize_compressed(&*readed_proving_key_file).unwrap()
+    Ok(VerifyingKeyWithLink::<E>::deserialize_compressed(&*readed_verifying_key_file)?)
 }
 
 pub fn proving_key_to_strng<E:Pairing>(
@@ -77,9 +165,30 @@ pub fn proving_key_to_strng<E:Pairing>(
     format!("{:#?}", proving_key)
 }
 
-pub fn abs_path(relative_path: &str) -> String {
-    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    path.push(relative_path);
-    path.to_string_lossy().to_string()
-}
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    #[test]
+    fn unreadable_keys_are_errors() {
+        assert!(matches!(read_verifying_key_from_file::<Bn254>("/nonexistent/vk.bin"), Err(Error::Io(_))));
+
+        let path = std::env::temp_dir().join(format!("keys_{}_garbage_pk.bin", std::process::id()));
+        write(&path, b"not a key").unwrap();
+        assert!(matches!(
+            read_compressed_proving_key_from_file::<Bn254>(&path.to_string_lossy()),
+            Err(Error::Serialization(_))
+        ));
+    }
+
+    #[test]
+    fn proving_key_bytes_round_trip() {
+        let proving_key = crate::circuit::test_util::range_pk(1);
+        let (pk_bytes, vk_bytes) = compressed_proving_key_bytes::<Bn254>(&proving_key).unwrap();
+        assert!(proving_key_from_bytes::<Bn254>(&pk_bytes).unwrap() == proving_key);
+        assert!(VerifyingKey::<Bn254>::deserialize_compressed(&*vk_bytes).unwrap() == proving_key.vk);
+    }
+}
@@ -0,0 +1,270 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::CanonicalDeserialize;
+use hex::ToHex;
+use legogroth16::{Proof, VerifyingKey};
+use std::{
+    collections::BTreeMap,
+    fs::read,
+};
+
+use crate::{
+    commitment::{Commitment, OpeningKey},
+    error::{Error, Result},
+    prover::proof_to_bytes,
+    transaction::{self, FileTransaction},
+};
+
+pub struct Member<E:Pairing> {
+    pub proof : Proof<E>,
+    // unknown for members that only published their proof
+    pub opening_key : Option<OpeningKey<E>>,
+}
+
+impl<E:Pairing> Member<E> {
+    pub fn commitment(&self) -> Commitment<E> {
+        Commitment::from_proof(&self.proof)
+    }
+}
+
+// which proofs are included in an aggregated commitment
+// the aggregate is updated with every change and can be checked against the members
+pub struct AggregateLedger<E:Pairing> {
+    members : BTreeMap<String, Member<E>>,
+    aggregate : Commitment<E>,
+}
+
+impl<E:Pairing> Default for AggregateLedger<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E:Pairing> AggregateLedger<E> {
+    pub fn new() -> Self {
+        Self {
+            members : BTreeMap::new(),
+            aggregate : Commitment::new(E::G1Affine::zero()),
+        }
+    }
+
+    pub fn member_ids(&self) -> Vec<String> {
+        self.members.keys().cloned().collect()
+    }
+
+    pub fn member(&self, id : &str) -> Option<&Member<E>> {
+        self.members.get(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn aggregate(&self) -> Commitment<E> {
+        self.aggregate
+    }
+
+    pub fn add_member(&mut self, id : &str, proof : Proof<E>, opening_key : Option<OpeningKey<E>>) -> Result<()> {
+        if self.members.contains_key(id) {
+            return Err(Error::Ledger(format!("member {} already exists", id)));
+        }
+        let member = Member { proof, opening_key };
+        self.aggregate = self.aggregate.add(&member.commitment());
+        self.members.insert(id.to_string(), member);
+        Ok(())
+    }
+
+    pub fn remove_member(&mut self, id : &str) -> Result<Member<E>> {
+        let member = self.members
+            .remove(id)
+            .ok_or_else(|| Error::Ledger(format!("member {} not found", id)))?;
+        self.aggregate = self.aggregate.sub(&member.commitment());
+        Ok(member)
+    }
+
+    // replaces the proof of an existing member, returns the previous one
+    pub fn update_member(&mut self, id : &str, proof : Proof<E>, opening_key : Option<OpeningKey<E>>) -> Result<Member<E>> {
+        let before = self.remove_member(id)?;
+        self.add_member(id, proof, opening_key)?;
+        Ok(before)
+    }
+
+    // sum of the member commitments, independent of the stored aggregate
+    pub fn recompute_aggregate(&self) -> Commitment<E> {
+        self.members
+            .values()
+            .fold(Commitment::new(E::G1Affine::zero()), |aggregate, member| aggregate.add(&member.commitment()))
+    }
+
+    // None if any member has no opening key
+    pub fn aggregate_opening_key(&self) -> Result<Option<OpeningKey<E>>> {
+        let mut opening_keys = self.members.values().map(|member| member.opening_key.as_ref());
+        let mut aggregated = match opening_keys.next() {
+            Some(Some(opening_key)) => opening_key.clone(),
+            _ => return Ok(None),
+        };
+        for opening_key in opening_keys {
+            match opening_key {
+                Some(opening_key) => aggregated = aggregated.add(opening_key)?,
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(aggregated))
+    }
+
+    // stored aggregate == sum of members, and every known opening key opens its commitment
+    pub fn check(&self, vk : Option<&VerifyingKey<E>>) -> Result<bool> {
+        if self.aggregate != self.recompute_aggregate() {
+            return Ok(false);
+        }
+        if let Some(vk) = vk {
+            for member in self.members.values() {
+                if let Some(opening_key) = &member.opening_key {
+                    if opening_key.commit(vk)? != member.commitment() {
+                        return Ok(false);
+                    }
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    // resets the stored aggregate to the sum of the members
+    pub fn repair(&mut self) {
+        self.aggregate = self.recompute_aggregate();
+    }
+
+    // {"aggregate" : hex, "members" : [{"id", "proof" : hex, "opening_key" : {m, v} | null}]}
+    pub fn to_json(&self) -> String {
+        let members = self.members
+            .iter()
+            .map(|(id, member)| serde_json::json!({
+                "id" : id,
+                "proof" : proof_to_bytes(&member.proof).encode_hex::<String>(),
+                "opening_key" : member.opening_key
+                    .as_ref()
+                    .map(|opening_key| serde_json::from_str::<serde_json::Value>(&opening_key.to_json()).unwrap()),
+            }))
+            .collect::<Vec<_>>();
+
+        serde_json::to_string(&serde_json::json!({
+            "aggregate" : self.aggregate.to_bytes().encode_hex::<String>(),
+            "members" : members
+        })).unwrap()
+    }
+
+    pub fn from_json(json : &str) -> Result<Self> {
+        let ledger_json : serde_json::Value = serde_json::from_str(json)
+            .map_err(|e| Error::Ledger(e.to_string()))?;
+
+        let mut ledger = Self::new();
+        for member in ledger_json["members"].as_array().ok_or_else(|| Error::Ledger("no members".to_string()))? {
+            let id = member["id"]
+                .as_str()
+                .ok_or_else(|| Error::Ledger("member without id".to_string()))?;
+            let proof = Proof::<E>::deserialize_compressed(&*hex_bytes(&member["proof"])?)?;
+            let opening_key = match &member["opening_key"] {
+                serde_json::Value::Null => None,
+                opening_key => Some(OpeningKey::<E>::from_json(&opening_key.to_string())?),
+            };
+            ledger.add_member(id, proof, opening_key)?;
+        }
+
+        // keep the stored aggregate as is, so check() can detect a mismatch
+        ledger.aggregate = Commitment::from_bytes(&hex_bytes(&ledger_json["aggregate"])?)?;
+        Ok(ledger)
+    }
+
+    pub fn read_from_file(path : &str) -> Result<Self> {
+        // a save interrupted by a crash is finished or rolled back first
+        transaction::recover(journal_path(path).as_str())?;
+        let json = read(path)?;
+        Self::from_json(&String::from_utf8_lossy(&json))
+    }
+
+    pub fn write_to_file(&self, path : &str) -> Result<()> {
+        let mut txn = FileTransaction::new(journal_path(path).as_str());
+        txn.write(path, self.to_json());
+        txn.commit()
+    }
+}
+
+fn journal_path(path : &str) -> String {
+    format!("{}.journal", path)
+}
+
+fn hex_bytes(value : &serde_json::Value) -> Result<Vec<u8>> {
+    let hex_string = value
+        .as_str()
+        .ok_or_else(|| Error::Ledger(format!("expected hex string, got {}", value)))?;
+    hex::decode(hex_string).map_err(|e| Error::Ledger(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Bn254;
+    use crate::circuit::test_util::{prove_values, range_pk};
+
+    #[test]
+    fn aggregate_follows_members() {
+        let pk = range_pk(1);
+        let (proof_a, key_a) = prove_values(&pk, &[10], 1);
+        let (proof_b, key_b) = prove_values(&pk, &[20], 2);
+        let (proof_c, key_c) = prove_values(&pk, &[30], 3);
+
+        let mut ledger = AggregateLedger::<Bn254>::new();
+        ledger.add_member("a", proof_a, Some(key_a.clone())).unwrap();
+        ledger.add_member("b", proof_b.clone(), Some(key_b)).unwrap();
+        assert!(matches!(ledger.add_member("a", proof_c.clone(), None), Err(Error::Ledger(_))));
+
+        let before = ledger.update_member("b", proof_c.clone(), Some(key_c.clone())).unwrap();
+        assert_eq!(before.proof.d, proof_b.d);
+        assert_eq!(ledger.aggregate_opening_key().unwrap(), Some(key_a.add(&key_c).unwrap()));
+        assert_eq!(ledger.aggregate(), key_a.add(&key_c).unwrap().commit(&pk.vk).unwrap());
+        assert!(ledger.check(Some(&pk.vk)).unwrap());
+
+        ledger.remove_member("a").unwrap();
+        assert!(matches!(ledger.remove_member("a"), Err(Error::Ledger(_))));
+        assert_eq!(ledger.member_ids(), vec!["b".to_string()]);
+        assert_eq!(ledger.aggregate(), Commitment::from_proof(&proof_c));
+    }
+
+    #[test]
+    fn json_round_trip_keeps_a_tampered_aggregate_detectable() {
+        let pk = range_pk(1);
+        let (proof_a, key_a) = prove_values(&pk, &[10], 1);
+        let (proof_b, _) = prove_values(&pk, &[20], 2);
+
+        let mut ledger = AggregateLedger::<Bn254>::new();
+        ledger.add_member("a", proof_a.clone(), Some(key_a)).unwrap();
+        ledger.add_member("b", proof_b, None).unwrap();
+        assert_eq!(ledger.aggregate_opening_key().unwrap(), None);
+
+        let restored = AggregateLedger::<Bn254>::from_json(&ledger.to_json()).unwrap();
+        assert_eq!(restored.aggregate(), ledger.aggregate());
+        assert!(restored.check(Some(&pk.vk)).unwrap());
+
+        let mut json : serde_json::Value = serde_json::from_str(&ledger.to_json()).unwrap();
+        json["aggregate"] = serde_json::json!(Commitment::<Bn254>::from_proof(&proof_a).to_bytes().encode_hex::<String>());
+        let mut tampered = AggregateLedger::<Bn254>::from_json(&json.to_string()).unwrap();
+        assert!(!tampered.check(None).unwrap());
+        tampered.repair();
+        assert!(tampered.check(None).unwrap());
+    }
+
+    #[test]
+    fn wrong_opening_key_fails_the_check() {
+        let pk = range_pk(1);
+        let (proof_a, _) = prove_values(&pk, &[10], 1);
+        let (_, key_b) = prove_values(&pk, &[20], 2);
+
+        let mut ledger = AggregateLedger::<Bn254>::new();
+        ledger.add_member("a", proof_a, Some(key_b)).unwrap();
+        assert!(ledger.check(None).unwrap());
+        assert!(!ledger.check(Some(&pk.vk)).unwrap());
+    }
+}
@@ -0,0 +1,2577 @@
+use std::collections::HashMap;
+
+use ark_bn254::Bn254;
+use ark_bls12_381::Bls12_381;
+use ark_bls12_377::Bls12_377;
+use std::{
+  fs::{write}
+};
+
+use ark_serialize::CanonicalDeserialize;
+use napi::bindgen_prelude::{Buffer, Either};
+use napi_derive::napi;
+
+use crate::{
+  aggregation,
+  commitment,
+  context,
+  field,
+  keys,
+  ledger,
+  prover,
+  range,
+  setup,
+  sigma,
+  transaction,
+  verifier,
+};
+
+// path : pk, vk ans saving
+// pk   : circuit_bn128_pk.bin
+// vk   : circuit_bn128_vk.bin
+// proof: circuit_bn128_proof.bin
+#[napi]
+pub fn setup_from_circom_r1cs_bn128(
+  r1cs_file_path : String,
+  commit_witness_count : u32,
+  seed:u32,
+  path_pk : String,
+  path_vk : String,
+) -> napi::Result<()> {
+  let(_, proving_key, _) = setup::setup_from_circom_r1cs_bn128(
+    r1cs_file_path,
+    commit_witness_count as usize,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  keys::write_bn128_proving_key(proving_key, path_pk.as_str(),path_vk.as_str()).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn setup_from_circom_r1cs_bls12_381(
+  r1cs_file_path : String,
+  commit_witness_count : u32,
+  seed : u32,
+  path_pk : String,
+  path_vk : String,
+) -> napi::Result<()> {
+  let(_, proving_key, _) = setup::setup_from_circom_r1cs_bls12_381(
+    r1cs_file_path,
+    commit_witness_count as usize,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  keys::write_bls12_381_proving_key(proving_key, path_pk.as_str(),path_vk.as_str()).map_err(to_napi_error)
+}
+
+// content of the pk and vk files
+#[napi(object)]
+pub struct KeyBuffers {
+  pub pk : Buffer,
+  pub vk : Buffer,
+}
+
+// content of the proof file and the opening key json
+#[napi(object)]
+pub struct ProofBuffer {
+  pub proof : Buffer,
+  pub opening_key : String,
+}
+
+// r1cs (and wasm) from memory instead of files : embedded, fetched or integrity-checked circuits
+// the keys are returned, not written
+#[napi]
+pub fn setup_from_circom_r1cs_buffer_bn128(
+  r1cs : Buffer,
+  commit_witness_count : u32,
+  seed : u32,
+) -> napi::Result<KeyBuffers> {
+  let proving_key = setup::setup_from_circom_r1cs_bytes::<Bn254>(
+    &r1cs,
+    commit_witness_count as usize,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  let (pk, vk) = keys::compressed_proving_key_bytes::<Bn254>(&proving_key).map_err(to_napi_error)?;
+  Ok(KeyBuffers { pk : pk.into(), vk : vk.into() })
+}
+
+#[napi]
+pub fn setup_from_circom_r1cs_buffer_bls12_381(
+  r1cs : Buffer,
+  commit_witness_count : u32,
+  seed : u32,
+) -> napi::Result<KeyBuffers> {
+  let proving_key = setup::setup_from_circom_r1cs_bytes::<Bls12_381>(
+    &r1cs,
+    commit_witness_count as usize,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  let (pk, vk) = keys::compressed_proving_key_bytes::<Bls12_381>(&proving_key).map_err(to_napi_error)?;
+  Ok(KeyBuffers { pk : pk.into(), vk : vk.into() })
+}
+
+#[napi]
+pub fn prove_range_bn128(
+  r1cs_file_path : String,
+  wasm_file_path : String,
+  pk_file_path : String,
+  proof_file_path : String,
+  input_string: String,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<()> {
+  let value = prover::make_range_inputs::<Bn254>(
+    input_string,
+    field::ReduceMode::from_flag(reduce)
+  ).map_err(to_napi_error)?;
+  let mut inputs= HashMap::new();
+  inputs.insert("value".to_string(), vec![value]);
+
+  let opening_key = prover::prove::<Bn254, _>(
+    r1cs_file_path.as_str(), 
+    pk_file_path.as_str(), 
+    wasm_file_path.as_str(), 
+    proof_file_path.as_str(),
+    1usize,
+    inputs.clone(), 
+    seed as u64
+  ).map_err(to_napi_error)?;
+  write_opening_key(proof_file_path.as_str(), opening_key)?;
+
+  Ok(())
+}
+
+#[napi]
+pub fn prove_range_bls12_381(
+  r1cs_file_path : String,
+  wasm_file_path : String,
+  pk_file_path : String,
+  proof_file_path : String,
+  input_string: String,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<()> {
+  let value = prover::make_range_inputs::<Bls12_381>(
+    input_string,
+    field::ReduceMode::from_flag(reduce)
+  ).map_err(to_napi_error)?;
+  let mut inputs= HashMap ::new();
+  inputs.insert("value".to_string(), vec![value]);
+
+  let opening_key = prover::prove::<Bls12_381, _>(
+    r1cs_file_path.as_str(), 
+    pk_file_path.as_str(), 
+    wasm_file_path.as_str(), 
+    proof_file_path.as_str(),
+    1usize,
+    inputs.clone(), 
+    seed as u64
+  ).map_err(to_napi_error)?;
+  write_opening_key(proof_file_path.as_str(), opening_key)?;
+
+  Ok(())
+}
+
+#[napi]
+pub fn verify_range_bn128(
+  vk_path : String,
+  proof_file_path : String,
+) -> napi::Result<bool> {
+  verifier::verify::<Bn254>(
+    vk_path.as_str(), 
+    proof_file_path.as_str(), 
+    vec![]
+  ).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn verify_range_bls12_381(
+  vk_path : String,
+  proof_file_path : String
+) -> napi::Result<bool> {
+  verifier::verify::<Bls12_381>(
+    vk_path.as_str(), 
+    proof_file_path.as_str(), 
+    vec![]
+  ).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn verify_range_bls12_377(
+  vk_path : String,
+  proof_file_path : String
+) -> napi::Result<bool> {
+  verifier::verify::<Bls12_377>(
+    vk_path.as_str(), 
+    proof_file_path.as_str(), 
+    vec![]
+  ).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn get_proof_bn128(
+  proof_file_path : String
+) -> napi::Result<String> {
+  prover::proof_to_string_from_file::<Bn254>(proof_file_path.as_str()).map_err(to_napi_error)
+}
+
+#[napi] 
+pub fn aggregate_proof_commitment_bn128(
+  proof_file_paths : Vec<String>,
+  save_file_path : String
+) -> napi::Result<()> {
+  prover::aggregate_proof_commitment::<Bn254>(proof_file_paths, save_file_path.as_str()).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn aggregate_proof_commitment_bls12_381(
+  proof_file_paths : Vec<String>,
+  save_file_path : String
+) -> napi::Result<()> {
+  prover::aggregate_proof_commitment::<Bls12_381>(proof_file_paths, save_file_path.as_str()).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn aggregate_proof_commitment_bls12_377(
+  proof_file_paths : Vec<String>,
+  save_file_path : String
+) -> napi::Result<()> {
+  prover::aggregate_proof_commitment::<Bls12_377>(proof_file_paths, save_file_path.as_str()).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn get_aggregated_commitment_bn128(
+  aggregated_commitment_file_path : String
+) -> napi::Result<String> {
+  prover::get_aggregated_commitment::<Bn254>(aggregated_commitment_file_path.as_str()).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn get_aggregated_commitment_bls12_381(
+  aggregated_commitment_file_path : String
+) -> napi::Result<String> {
+  prover::get_aggregated_commitment::<Bls12_381>(aggregated_commitment_file_path.as_str()).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn get_aggregated_commitment_bls12_377(
+  aggregated_commitment_file_path : String
+) -> napi::Result<String> {
+  prover::get_aggregated_commitment::<Bls12_377>(aggregated_commitment_file_path.as_str()).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn aggregate_opening_keys_bn128(
+  opening_key_file_paths : Vec<String>,
+  save_file_path : String
+) -> napi::Result<()> {
+  prover::aggregated_pedersen_commitment_opening_keys::<Bn254>(opening_key_file_paths, save_file_path.as_str()).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn aggregate_opening_keys_bls12_381(
+  opening_key_file_paths : Vec<String>,
+  save_file_path : String
+) -> napi::Result<()> {
+  prover::aggregated_pedersen_commitment_opening_keys::<Bls12_381>(opening_key_file_paths, save_file_path.as_str()).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn aggregate_opening_keys_bls12_377(
+  opening_key_file_paths : Vec<String>,
+  save_file_path : String
+) -> napi::Result<()> {
+  prover::aggregated_pedersen_commitment_opening_keys::<Bls12_377>(opening_key_file_paths, save_file_path.as_str()).map_err(to_napi_error)
+}
+
+// update_values : one value (range_proof) or one value per committed witness (range_vector_64xN)
+fn update_values_list(update_values : Either<String, Vec<String>>) -> Vec<String> {
+  match update_values {
+    Either::A(value) => vec![value],
+    Either::B(values) => values,
+  }
+}
+
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn update_aggregated_commitment_bn128(
+  r1cs_file_path : String,
+  key_file_path : String,
+  wasm_file_path : String,
+  proof_file_path : String,
+  aggregated_commitment_file_path : String,
+  aggregated_opening_key_file_path : String,
+  update_values : Either<String, Vec<String>>,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<()> {
+  prover::update_aggregated_commitment::<Bn254>(
+    r1cs_file_path.as_str(), 
+    key_file_path.as_str(), 
+    wasm_file_path.as_str(),
+    proof_file_path.as_str(), 
+    aggregated_commitment_file_path.as_str(), 
+    aggregated_opening_key_file_path.as_str(), 
+    update_values_list(update_values),
+    field::ReduceMode::from_flag(reduce),
+    seed as u64
+  ).map_err(to_napi_error)
+}
+
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn update_aggregated_commitment_bls12_381(
+  r1cs_file_path : String,
+  key_file_path : String,
+  wasm_file_path : String,
+  proof_file_path : String,
+  aggregated_commitment_file_path : String,
+  aggregated_opening_key_file_path : String,
+  update_values : Either<String, Vec<String>>,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<()> {
+  prover::update_aggregated_commitment::<Bls12_381>(
+    r1cs_file_path.as_str(), 
+    key_file_path.as_str(), 
+    wasm_file_path.as_str(),
+    proof_file_path.as_str(), 
+    aggregated_commitment_file_path.as_str(), 
+    aggregated_opening_key_file_path.as_str(), 
+    update_values_list(update_values),
+    field::ReduceMode::from_flag(reduce),
+    seed as u64
+  ).map_err(to_napi_error)
+}
+
+// verifies the member proof and opening key, then subtracts them from the aggregated files
+// opening_key_file_path : defaults to <proof>_opening_key.json
+// public_inputs : public inputs of the member proof, omit for circuits without public inputs
+#[napi]
+pub fn remove_from_aggregate_bn128(
+  vk_file_path : String,
+  proof_file_path : String,
+  opening_key_file_path : Option<String>,
+  aggregated_commitment_file_path : String,
+  aggregated_opening_key_file_path : String,
+  public_inputs : Option<Vec<String>>
+) -> napi::Result<()> {
+  prover::remove_from_aggregate_file::<Bn254>(
+    vk_file_path.as_str(),
+    proof_file_path.as_str(),
+    opening_key_file_path.as_deref(),
+    aggregated_commitment_file_path.as_str(),
+    aggregated_opening_key_file_path.as_str(),
+    &to_inputs::<Bn254>(public_inputs)?
+  ).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn remove_from_aggregate_bls12_381(
+  vk_file_path : String,
+  proof_file_path : String,
+  opening_key_file_path : Option<String>,
+  aggregated_commitment_file_path : String,
+  aggregated_opening_key_file_path : String,
+  public_inputs : Option<Vec<String>>
+) -> napi::Result<()> {
+  prover::remove_from_aggregate_file::<Bls12_381>(
+    vk_file_path.as_str(),
+    proof_file_path.as_str(),
+    opening_key_file_path.as_deref(),
+    aggregated_commitment_file_path.as_str(),
+    aggregated_opening_key_file_path.as_str(),
+    &to_inputs::<Bls12_381>(public_inputs)?
+  ).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn remove_from_aggregate_bls12_377(
+  vk_file_path : String,
+  proof_file_path : String,
+  opening_key_file_path : Option<String>,
+  aggregated_commitment_file_path : String,
+  aggregated_opening_key_file_path : String,
+  public_inputs : Option<Vec<String>>
+) -> napi::Result<()> {
+  prover::remove_from_aggregate_file::<Bls12_377>(
+    vk_file_path.as_str(),
+    proof_file_path.as_str(),
+    opening_key_file_path.as_deref(),
+    aggregated_commitment_file_path.as_str(),
+    aggregated_opening_key_file_path.as_str(),
+    &to_inputs::<Bls12_377>(public_inputs)?
+  ).map_err(to_napi_error)
+}
+
+// reveals the total of an aggregated opening key with a proof of knowledge of its blinding
+// returns {"m" : hex, "blinding_proof" : hex}
+#[napi]
+pub fn prove_aggregated_total_bn128(
+  vk_file_path : String,
+  aggregated_opening_key_file_path : String
+) -> napi::Result<String> {
+  prove_aggregated_total::<Bn254>(vk_file_path, aggregated_opening_key_file_path)
+}
+
+#[napi]
+pub fn prove_aggregated_total_bls12_381(
+  vk_file_path : String,
+  aggregated_opening_key_file_path : String
+) -> napi::Result<String> {
+  prove_aggregated_total::<Bls12_381>(vk_file_path, aggregated_opening_key_file_path)
+}
+
+#[napi]
+pub fn prove_aggregated_total_bls12_377(
+  vk_file_path : String,
+  aggregated_opening_key_file_path : String
+) -> napi::Result<String> {
+  prove_aggregated_total::<Bls12_377>(vk_file_path, aggregated_opening_key_file_path)
+}
+
+// true if the aggregated commitment opens to total
+// total, blinding : hex as in the opening key files
+// blinding_proof  : from proveAggregatedTotal, used when the blinding is not revealed
+#[napi]
+pub fn verify_aggregated_total_bn128(
+  aggregated_commitment_file_path : String,
+  vk_file_path : String,
+  total : String,
+  blinding : Option<String>,
+  blinding_proof : Option<String>
+) -> napi::Result<bool> {
+  verify_aggregated_total::<Bn254>(aggregated_commitment_file_path, vk_file_path, total, blinding, blinding_proof)
+}
+
+#[napi]
+pub fn verify_aggregated_total_bls12_381(
+  aggregated_commitment_file_path : String,
+  vk_file_path : String,
+  total : String,
+  blinding : Option<String>,
+  blinding_proof : Option<String>
+) -> napi::Result<bool> {
+  verify_aggregated_total::<Bls12_381>(aggregated_commitment_file_path, vk_file_path, total, blinding, blinding_proof)
+}
+
+#[napi]
+pub fn verify_aggregated_total_bls12_377(
+  aggregated_commitment_file_path : String,
+  vk_file_path : String,
+  total : String,
+  blinding : Option<String>,
+  blinding_proof : Option<String>
+) -> napi::Result<bool> {
+  verify_aggregated_total::<Bls12_377>(aggregated_commitment_file_path, vk_file_path, total, blinding, blinding_proof)
+}
+
+fn prove_aggregated_total<E: ark_ec::pairing::Pairing>(
+  vk_file_path : String,
+  aggregated_opening_key_file_path : String
+) -> napi::Result<String> {
+  let vk = keys::read_verifying_key_from_file::<E>(vk_file_path.as_str()).map_err(to_napi_error)?;
+  let aggregated_opening_key = commitment::OpeningKey::<E>::read_from_file(aggregated_opening_key_file_path.as_str())
+    .map_err(to_napi_error)?;
+  let blinding_proof = prover::prove_aggregated_total::<E>(&vk, &aggregated_opening_key)
+    .map_err(to_napi_error)?;
+
+  let opening_key_json : serde_json::Value = serde_json::from_str(&aggregated_opening_key.to_json())
+    .map_err(to_napi_error)?;
+  Ok(serde_json::json!({
+    "m" : opening_key_json["m"],
+    "blinding_proof" : hex::encode(blinding_proof.to_bytes())
+  }).to_string())
+}
+
+fn verify_aggregated_total<E: ark_ec::pairing::Pairing>(
+  aggregated_commitment_file_path : String,
+  vk_file_path : String,
+  total : String,
+  blinding : Option<String>,
+  blinding_proof : Option<String>
+) -> napi::Result<bool> {
+  let vk = keys::read_verifying_key_from_file::<E>(vk_file_path.as_str()).map_err(to_napi_error)?;
+  let aggregated_commitment = commitment::Commitment::<E>::read_from_file(aggregated_commitment_file_path.as_str())
+    .map_err(to_napi_error)?;
+  let total = field::parse_hex_field_element(total.as_str(), field::ReduceMode::Strict)
+    .map_err(to_napi_error)?;
+  let blinding = blinding
+    .map(|v| field::parse_hex_field_element(v.as_str(), field::ReduceMode::Strict))
+    .transpose()
+    .map_err(to_napi_error)?;
+  let blinding_proof = blinding_proof
+    .map(|proof| sigma::SchnorrProof::from_bytes(&hex::decode(proof).map_err(to_napi_error)?).map_err(to_napi_error))
+    .transpose()?;
+
+  verifier::verify_aggregated_total::<E>(
+    &vk,
+    &aggregated_commitment,
+    &[total],
+    blinding,
+    blinding_proof.as_ref()
+  ).map_err(to_napi_error)
+}
+
+// range proof about the hidden total of an aggregated commitment, with the range_proof key
+// bit_width : n of the circuit
+// limit : total <= limit, otherwise 0 <= total < 2^n of the circuit, limit must be < 2^n
+// the aggregated opening key stays private, only the proof is written
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn prove_aggregate_range_bn128(
+  r1cs_file_path : String,
+  wasm_file_path : String,
+  pk_file_path : String,
+  aggregated_opening_key_file_path : String,
+  proof_file_path : String,
+  bit_width : u32,
+  limit : Option<String>,
+  seed : u32
+) -> napi::Result<()> {
+  prove_aggregate_range::<Bn254>(
+    r1cs_file_path,
+    wasm_file_path,
+    pk_file_path,
+    aggregated_opening_key_file_path,
+    proof_file_path,
+    bit_width,
+    limit,
+    seed
+  )
+}
+
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn prove_aggregate_range_bls12_381(
+  r1cs_file_path : String,
+  wasm_file_path : String,
+  pk_file_path : String,
+  aggregated_opening_key_file_path : String,
+  proof_file_path : String,
+  bit_width : u32,
+  limit : Option<String>,
+  seed : u32
+) -> napi::Result<()> {
+  prove_aggregate_range::<Bls12_381>(
+    r1cs_file_path,
+    wasm_file_path,
+    pk_file_path,
+    aggregated_opening_key_file_path,
+    proof_file_path,
+    bit_width,
+    limit,
+    seed
+  )
+}
+
+// bit_width and limit must be the same as in prove_aggregate_range
+// public_inputs : public inputs of the range circuit, omit for circuits without public inputs
+#[napi]
+pub fn verify_aggregate_range_bn128(
+  vk_file_path : String,
+  proof_file_path : String,
+  aggregated_commitment_file_path : String,
+  bit_width : u32,
+  limit : Option<String>,
+  public_inputs : Option<Vec<String>>
+) -> napi::Result<bool> {
+  verify_aggregate_range::<Bn254>(vk_file_path, proof_file_path, aggregated_commitment_file_path, bit_width, limit, public_inputs)
+}
+
+#[napi]
+pub fn verify_aggregate_range_bls12_381(
+  vk_file_path : String,
+  proof_file_path : String,
+  aggregated_commitment_file_path : String,
+  bit_width : u32,
+  limit : Option<String>,
+  public_inputs : Option<Vec<String>>
+) -> napi::Result<bool> {
+  verify_aggregate_range::<Bls12_381>(vk_file_path, proof_file_path, aggregated_commitment_file_path, bit_width, limit, public_inputs)
+}
+
+#[napi]
+pub fn verify_aggregate_range_bls12_377(
+  vk_file_path : String,
+  proof_file_path : String,
+  aggregated_commitment_file_path : String,
+  bit_width : u32,
+  limit : Option<String>,
+  public_inputs : Option<Vec<String>>
+) -> napi::Result<bool> {
+  verify_aggregate_range::<Bls12_377>(vk_file_path, proof_file_path, aggregated_commitment_file_path, bit_width, limit, public_inputs)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prove_aggregate_range<E: ark_ec::pairing::Pairing>(
+  r1cs_file_path : String,
+  wasm_file_path : String,
+  pk_file_path : String,
+  aggregated_opening_key_file_path : String,
+  proof_file_path : String,
+  bit_width : u32,
+  limit : Option<String>,
+  seed : u32
+) -> napi::Result<()> {
+  let aggregated_opening_key = commitment::OpeningKey::<E>::read_from_file(aggregated_opening_key_file_path.as_str())
+    .map_err(to_napi_error)?;
+  let limit = limit
+    .map(|limit| prover::make_range_inputs::<E>(limit, field::ReduceMode::Strict))
+    .transpose()
+    .map_err(to_napi_error)?;
+
+  let proof = prover::prove_aggregate_range::<E>(
+    r1cs_file_path.as_str(),
+    pk_file_path.as_str(),
+    wasm_file_path.as_str(),
+    &aggregated_opening_key,
+    bit_width as usize,
+    limit,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  write(
+    proof_file_path.as_str(),
+    prover::proof_to_bytes(&proof)
+  ).map_err(to_napi_error)
+}
+
+fn verify_aggregate_range<E: ark_ec::pairing::Pairing>(
+  vk_file_path : String,
+  proof_file_path : String,
+  aggregated_commitment_file_path : String,
+  bit_width : u32,
+  limit : Option<String>,
+  public_inputs : Option<Vec<String>>
+) -> napi::Result<bool> {
+  let vk = keys::read_verifying_key_from_file::<E>(vk_file_path.as_str()).map_err(to_napi_error)?;
+  let proof = prover::read_proof_from_file::<E>(proof_file_path.as_str()).map_err(to_napi_error)?;
+  let aggregated_commitment = commitment::Commitment::<E>::read_from_file(aggregated_commitment_file_path.as_str())
+    .map_err(to_napi_error)?;
+  let limit = limit
+    .map(|limit| prover::make_range_inputs::<E>(limit, field::ReduceMode::Strict))
+    .transpose()
+    .map_err(to_napi_error)?;
+
+  verifier::verify_aggregate_range::<E>(
+    &vk,
+    &proof,
+    &aggregated_commitment,
+    bit_width as usize,
+    limit,
+    &to_inputs::<E>(public_inputs)?
+  )
+    .map_err(to_napi_error)
+}
+
+// proves that proof.d and an external pedersen commitment commit to the same messages
+// external_bases          : hex compressed g1 points, one per committed witness
+// external_blinding_base  : hex compressed g1 point h
+// external_blinding       : hex blinding of the external commitment
+// returns the hex encoded equality proof
+#[napi]
+pub fn prove_commitment_equality_bn128(
+  vk_file_path : String,
+  opening_key_file_path : String,
+  external_bases : Vec<String>,
+  external_blinding_base : String,
+  external_blinding : String
+) -> napi::Result<String> {
+  prove_commitment_equality::<Bn254>(
+    vk_file_path,
+    opening_key_file_path,
+    external_bases,
+    external_blinding_base,
+    external_blinding
+  )
+}
+
+#[napi]
+pub fn prove_commitment_equality_bls12_381(
+  vk_file_path : String,
+  opening_key_file_path : String,
+  external_bases : Vec<String>,
+  external_blinding_base : String,
+  external_blinding : String
+) -> napi::Result<String> {
+  prove_commitment_equality::<Bls12_381>(
+    vk_file_path,
+    opening_key_file_path,
+    external_bases,
+    external_blinding_base,
+    external_blinding
+  )
+}
+
+#[napi]
+pub fn prove_commitment_equality_bls12_377(
+  vk_file_path : String,
+  opening_key_file_path : String,
+  external_bases : Vec<String>,
+  external_blinding_base : String,
+  external_blinding : String
+) -> napi::Result<String> {
+  prove_commitment_equality::<Bls12_377>(
+    vk_file_path,
+    opening_key_file_path,
+    external_bases,
+    external_blinding_base,
+    external_blinding
+  )
+}
+
+// external_commitment : hex compressed g1 point
+#[napi]
+pub fn verify_commitment_equality_bn128(
+  vk_file_path : String,
+  proof_file_path : String,
+  external_commitment : String,
+  external_bases : Vec<String>,
+  external_blinding_base : String,
+  equality_proof : String
+) -> napi::Result<bool> {
+  verify_commitment_equality::<Bn254>(
+    vk_file_path,
+    proof_file_path,
+    external_commitment,
+    external_bases,
+    external_blinding_base,
+    equality_proof
+  )
+}
+
+#[napi]
+pub fn verify_commitment_equality_bls12_381(
+  vk_file_path : String,
+  proof_file_path : String,
+  external_commitment : String,
+  external_bases : Vec<String>,
+  external_blinding_base : String,
+  equality_proof : String
+) -> napi::Result<bool> {
+  verify_commitment_equality::<Bls12_381>(
+    vk_file_path,
+    proof_file_path,
+    external_commitment,
+    external_bases,
+    external_blinding_base,
+    equality_proof
+  )
+}
+
+#[napi]
+pub fn verify_commitment_equality_bls12_377(
+  vk_file_path : String,
+  proof_file_path : String,
+  external_commitment : String,
+  external_bases : Vec<String>,
+  external_blinding_base : String,
+  equality_proof : String
+) -> napi::Result<bool> {
+  verify_commitment_equality::<Bls12_377>(
+    vk_file_path,
+    proof_file_path,
+    external_commitment,
+    external_bases,
+    external_blinding_base,
+    equality_proof
+  )
+}
+
+fn prove_commitment_equality<E: ark_ec::pairing::Pairing>(
+  vk_file_path : String,
+  opening_key_file_path : String,
+  external_bases : Vec<String>,
+  external_blinding_base : String,
+  external_blinding : String
+) -> napi::Result<String> {
+  let vk = keys::read_verifying_key_from_file::<E>(vk_file_path.as_str()).map_err(to_napi_error)?;
+  let opening_key = commitment::OpeningKey::<E>::read_from_file(opening_key_file_path.as_str())
+    .map_err(to_napi_error)?;
+  let external_bases = to_pedersen_bases::<E>(external_bases, external_blinding_base)?;
+  let external_blinding = field::parse_hex_field_element(external_blinding.as_str(), field::ReduceMode::Strict)
+    .map_err(to_napi_error)?;
+
+  let equality_proof = prover::prove_commitment_equality::<E>(&vk, &opening_key, &external_bases, external_blinding)
+    .map_err(to_napi_error)?;
+  Ok(hex::encode(equality_proof.to_bytes()))
+}
+
+fn verify_commitment_equality<E: ark_ec::pairing::Pairing>(
+  vk_file_path : String,
+  proof_file_path : String,
+  external_commitment : String,
+  external_bases : Vec<String>,
+  external_blinding_base : String,
+  equality_proof : String
+) -> napi::Result<bool> {
+  let vk = keys::read_verifying_key_from_file::<E>(vk_file_path.as_str()).map_err(to_napi_error)?;
+  let proof = prover::read_proof_from_file::<E>(proof_file_path.as_str()).map_err(to_napi_error)?;
+  let external_commitment = hex_to_g1::<E>(external_commitment)?;
+  let external_bases = to_pedersen_bases::<E>(external_bases, external_blinding_base)?;
+  let equality_proof = sigma::EqualityProof::from_bytes(&hex::decode(equality_proof).map_err(to_napi_error)?)
+    .map_err(to_napi_error)?;
+
+  Ok(verifier::verify_commitment_equality::<E>(
+    &vk,
+    &proof,
+    &external_bases,
+    &external_commitment,
+    &equality_proof
+  ))
+}
+
+// INSECURE, tests and development only : srs for aggregating up to max_proofs proofs, derived from the seed.
+// whoever knows the seed can forge aggregated proofs, use an srs from a trusted setup ceremony in production
+#[napi]
+pub fn setup_insecure_aggregation_srs_bn128(
+  max_proofs : u32,
+  seed : u32,
+  srs_file_path : String
+) -> napi::Result<()> {
+  aggregation::setup_insecure_aggregation_srs::<Bn254>(max_proofs as usize, seed as u64, srs_file_path.as_str())
+    .map_err(to_napi_error)
+}
+
+#[napi]
+pub fn setup_insecure_aggregation_srs_bls12_381(
+  max_proofs : u32,
+  seed : u32,
+  srs_file_path : String
+) -> napi::Result<()> {
+  aggregation::setup_insecure_aggregation_srs::<Bls12_381>(max_proofs as usize, seed as u64, srs_file_path.as_str())
+    .map_err(to_napi_error)
+}
+
+#[napi]
+pub fn setup_insecure_aggregation_srs_bls12_377(
+  max_proofs : u32,
+  seed : u32,
+  srs_file_path : String
+) -> napi::Result<()> {
+  aggregation::setup_insecure_aggregation_srs::<Bls12_377>(max_proofs as usize, seed as u64, srs_file_path.as_str())
+    .map_err(to_napi_error)
+}
+
+// compresses proofs made with the same proving key into one aggregated proof
+// public_inputs : one array per proof, omit for circuits without public inputs
+#[napi]
+pub fn aggregate_proofs_bn128(
+  srs_file_path : String,
+  proof_file_paths : Vec<String>,
+  aggregated_proof_file_path : String,
+  public_inputs : Option<Vec<Vec<String>>>
+) -> napi::Result<()> {
+  aggregate_proofs::<Bn254>(srs_file_path, proof_file_paths, aggregated_proof_file_path, public_inputs)
+}
+
+#[napi]
+pub fn aggregate_proofs_bls12_381(
+  srs_file_path : String,
+  proof_file_paths : Vec<String>,
+  aggregated_proof_file_path : String,
+  public_inputs : Option<Vec<Vec<String>>>
+) -> napi::Result<()> {
+  aggregate_proofs::<Bls12_381>(srs_file_path, proof_file_paths, aggregated_proof_file_path, public_inputs)
+}
+
+#[napi]
+pub fn aggregate_proofs_bls12_377(
+  srs_file_path : String,
+  proof_file_paths : Vec<String>,
+  aggregated_proof_file_path : String,
+  public_inputs : Option<Vec<Vec<String>>>
+) -> napi::Result<()> {
+  aggregate_proofs::<Bls12_377>(srs_file_path, proof_file_paths, aggregated_proof_file_path, public_inputs)
+}
+
+// checks every proof of the aggregated proof and that their commitments sum to the aggregated commitment
+#[napi]
+pub fn verify_aggregated_proofs_bn128(
+  srs_file_path : String,
+  vk_file_path : String,
+  aggregated_proof_file_path : String,
+  aggregated_commitment_file_path : String,
+  public_inputs : Option<Vec<Vec<String>>>
+) -> napi::Result<bool> {
+  verify_aggregated_proofs::<Bn254>(
+    srs_file_path,
+    vk_file_path,
+    aggregated_proof_file_path,
+    aggregated_commitment_file_path,
+    public_inputs
+  )
+}
+
+#[napi]
+pub fn verify_aggregated_proofs_bls12_381(
+  srs_file_path : String,
+  vk_file_path : String,
+  aggregated_proof_file_path : String,
+  aggregated_commitment_file_path : String,
+  public_inputs : Option<Vec<Vec<String>>>
+) -> napi::Result<bool> {
+  verify_aggregated_proofs::<Bls12_381>(
+    srs_file_path,
+    vk_file_path,
+    aggregated_proof_file_path,
+    aggregated_commitment_file_path,
+    public_inputs
+  )
+}
+
+#[napi]
+pub fn verify_aggregated_proofs_bls12_377(
+  srs_file_path : String,
+  vk_file_path : String,
+  aggregated_proof_file_path : String,
+  aggregated_commitment_file_path : String,
+  public_inputs : Option<Vec<Vec<String>>>
+) -> napi::Result<bool> {
+  verify_aggregated_proofs::<Bls12_377>(
+    srs_file_path,
+    vk_file_path,
+    aggregated_proof_file_path,
+    aggregated_commitment_file_path,
+    public_inputs
+  )
+}
+
+fn aggregate_proofs<E: ark_ec::pairing::Pairing>(
+  srs_file_path : String,
+  proof_file_paths : Vec<String>,
+  aggregated_proof_file_path : String,
+  public_inputs : Option<Vec<Vec<String>>>
+) -> napi::Result<()> {
+  let srs = aggregation::read_aggregation_srs::<E>(srs_file_path.as_str()).map_err(to_napi_error)?;
+  let proofs = proof_file_paths
+    .iter()
+    .map(|path| prover::read_proof_from_file::<E>(path.as_str()))
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(to_napi_error)?;
+  let public_inputs = to_public_inputs::<E>(public_inputs, proofs.len())?;
+
+  let aggregated_proof = aggregation::aggregate::<E>(&srs, &proofs, &public_inputs).map_err(to_napi_error)?;
+  aggregated_proof.write_to_file(aggregated_proof_file_path.as_str()).map_err(to_napi_error)
+}
+
+fn verify_aggregated_proofs<E: ark_ec::pairing::Pairing>(
+  srs_file_path : String,
+  vk_file_path : String,
+  aggregated_proof_file_path : String,
+  aggregated_commitment_file_path : String,
+  public_inputs : Option<Vec<Vec<String>>>
+) -> napi::Result<bool> {
+  let srs = aggregation::read_aggregation_srs::<E>(srs_file_path.as_str()).map_err(to_napi_error)?;
+  let vk = keys::read_verifying_key_from_file::<E>(vk_file_path.as_str()).map_err(to_napi_error)?;
+  let aggregated_proof = aggregation::AggregatedProof::<E>::read_from_file(aggregated_proof_file_path.as_str())
+    .map_err(to_napi_error)?;
+  let aggregated_commitment = commitment::Commitment::<E>::read_from_file(aggregated_commitment_file_path.as_str())
+    .map_err(to_napi_error)?;
+  let public_inputs = to_public_inputs::<E>(public_inputs, aggregated_proof.d.len())?;
+
+  aggregation::verify_aggregated::<E>(&srs, &vk, &aggregated_proof, &public_inputs, &aggregated_commitment)
+    .map_err(to_napi_error)
+}
+
+// writes an unlinkable proof of the same statement
+// without pk_file_path proof.d is kept (same opening key), returns null
+// with pk_file_path proof.d is re-blinded too, returns the hex delta with  v' = v + delta
+//   opening_key_file_path : writes the updated opening key next to the rerandomized proof
+#[napi]
+pub fn rerandomize_proof_bn128(
+  vk_file_path : String,
+  proof_file_path : String,
+  rerandomized_proof_file_path : String,
+  pk_file_path : Option<String>,
+  opening_key_file_path : Option<String>
+) -> napi::Result<Option<String>> {
+  rerandomize_proof::<Bn254>(
+    vk_file_path,
+    proof_file_path,
+    rerandomized_proof_file_path,
+    pk_file_path,
+    opening_key_file_path
+  )
+}
+
+#[napi]
+pub fn rerandomize_proof_bls12_381(
+  vk_file_path : String,
+  proof_file_path : String,
+  rerandomized_proof_file_path : String,
+  pk_file_path : Option<String>,
+  opening_key_file_path : Option<String>
+) -> napi::Result<Option<String>> {
+  rerandomize_proof::<Bls12_381>(
+    vk_file_path,
+    proof_file_path,
+    rerandomized_proof_file_path,
+    pk_file_path,
+    opening_key_file_path
+  )
+}
+
+#[napi]
+pub fn rerandomize_proof_bls12_377(
+  vk_file_path : String,
+  proof_file_path : String,
+  rerandomized_proof_file_path : String,
+  pk_file_path : Option<String>,
+  opening_key_file_path : Option<String>
+) -> napi::Result<Option<String>> {
+  rerandomize_proof::<Bls12_377>(
+    vk_file_path,
+    proof_file_path,
+    rerandomized_proof_file_path,
+    pk_file_path,
+    opening_key_file_path
+  )
+}
+
+fn rerandomize_proof<E: ark_ec::pairing::Pairing>(
+  vk_file_path : String,
+  proof_file_path : String,
+  rerandomized_proof_file_path : String,
+  pk_file_path : Option<String>,
+  opening_key_file_path : Option<String>
+) -> napi::Result<Option<String>> {
+  let proof = prover::read_proof_from_file::<E>(proof_file_path.as_str()).map_err(to_napi_error)?;
+
+  let (rerandomized_proof, delta) = match pk_file_path {
+    Some(pk_file_path) => {
+      let proving_key = keys::read_compressed_proving_key_from_file::<E>(pk_file_path.as_str()).map_err(to_napi_error)?;
+      let (rerandomized_proof, delta) = prover::rerandomize_proof_and_commitment::<E>(&proving_key, &proof);
+      (rerandomized_proof, Some(delta))
+    },
+    None => {
+      let vk = keys::read_verifying_key_from_file::<E>(vk_file_path.as_str()).map_err(to_napi_error)?;
+      (prover::rerandomize_proof::<E>(&vk, &proof), None)
+    }
+  };
+
+  write(
+    rerandomized_proof_file_path.as_str(),
+    prover::proof_to_bytes(&rerandomized_proof)
+  ).map_err(to_napi_error)?;
+
+  if let Some(opening_key_file_path) = opening_key_file_path {
+    let opening_key = commitment::OpeningKey::<E>::read_from_file(opening_key_file_path.as_str())
+      .map_err(to_napi_error)?;
+    let v = opening_key.v + delta.unwrap_or_default();
+    let opening_key = commitment::OpeningKey::<E>::new(opening_key.m, v);
+    write_opening_key(rerandomized_proof_file_path.as_str(), opening_key.to_json())?;
+  }
+
+  Ok(delta.map(|delta| field::field_element_to_hex(&delta)))
+}
+
+// hex of the field element a context is hashed to, for circuits with a context public input
+#[napi]
+pub fn context_to_field_bn128(context : Buffer) -> String {
+  field::field_element_to_hex(&context::context_to_field::<ark_bn254::Fr>(&context))
+}
+
+#[napi]
+pub fn context_to_field_bls12_381(context : Buffer) -> String {
+  field::field_element_to_hex(&context::context_to_field::<ark_bls12_381::Fr>(&context))
+}
+
+#[napi]
+pub fn context_to_field_bls12_377(context : Buffer) -> String {
+  field::field_element_to_hex(&context::context_to_field::<ark_bls12_377::Fr>(&context))
+}
+
+// range proof bound to a context (circom/range/range_context_64.circom)
+// context : application bytes (session id, account id, epoch), the verifier needs the same bytes
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn prove_range_with_context_bn128(
+  r1cs_file_path : String,
+  wasm_file_path : String,
+  pk_file_path : String,
+  proof_file_path : String,
+  value : String,
+  context : Buffer,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<()> {
+  let value = prover::make_range_inputs::<Bn254>(value, field::ReduceMode::from_flag(reduce))
+    .map_err(to_napi_error)?;
+  range::check_circuit_and_key(r1cs_file_path.as_str(), wasm_file_path.as_str(), pk_file_path.as_str())
+    .map_err(to_napi_error)?;
+  let mut inputs = HashMap::new();
+  inputs.insert("value".to_string(), vec![value]);
+  let (name, context) = context::context_input(&context);
+  inputs.insert(name, context);
+
+  let opening_key = prover::prove::<Bn254, _>(
+    r1cs_file_path.as_str(),
+    pk_file_path.as_str(),
+    wasm_file_path.as_str(),
+    proof_file_path.as_str(),
+    1usize,
+    inputs,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  write_opening_key(proof_file_path.as_str(), opening_key)?;
+  Ok(())
+}
+
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn prove_range_with_context_bls12_381(
+  r1cs_file_path : String,
+  wasm_file_path : String,
+  pk_file_path : String,
+  proof_file_path : String,
+  value : String,
+  context : Buffer,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<()> {
+  let value = prover::make_range_inputs::<Bls12_381>(value, field::ReduceMode::from_flag(reduce))
+    .map_err(to_napi_error)?;
+  range::check_circuit_and_key(r1cs_file_path.as_str(), wasm_file_path.as_str(), pk_file_path.as_str())
+    .map_err(to_napi_error)?;
+  let mut inputs = HashMap::new();
+  inputs.insert("value".to_string(), vec![value]);
+  let (name, context) = context::context_input(&context);
+  inputs.insert(name, context);
+
+  let opening_key = prover::prove::<Bls12_381, _>(
+    r1cs_file_path.as_str(),
+    pk_file_path.as_str(),
+    wasm_file_path.as_str(),
+    proof_file_path.as_str(),
+    1usize,
+    inputs,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  write_opening_key(proof_file_path.as_str(), opening_key)?;
+  Ok(())
+}
+
+// public_inputs : public inputs other than the context, in circuit order
+#[napi]
+pub fn verify_with_context_bn128(
+  vk_file_path : String,
+  proof_file_path : String,
+  context : Buffer,
+  public_inputs : Option<Vec<String>>
+) -> napi::Result<bool> {
+  let public_inputs = public_inputs
+    .unwrap_or_default()
+    .into_iter()
+    .map(|input| prover::make_range_inputs::<Bn254>(input, field::ReduceMode::Strict))
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(to_napi_error)?;
+
+  verifier::verify_with_context::<Bn254>(
+    vk_file_path.as_str(),
+    proof_file_path.as_str(),
+    public_inputs,
+    &context
+  ).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn verify_with_context_bls12_381(
+  vk_file_path : String,
+  proof_file_path : String,
+  context : Buffer,
+  public_inputs : Option<Vec<String>>
+) -> napi::Result<bool> {
+  let public_inputs = public_inputs
+    .unwrap_or_default()
+    .into_iter()
+    .map(|input| prover::make_range_inputs::<Bls12_381>(input, field::ReduceMode::Strict))
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(to_napi_error)?;
+
+  verifier::verify_with_context::<Bls12_381>(
+    vk_file_path.as_str(),
+    proof_file_path.as_str(),
+    public_inputs,
+    &context
+  ).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn verify_with_context_bls12_377(
+  vk_file_path : String,
+  proof_file_path : String,
+  context : Buffer,
+  public_inputs : Option<Vec<String>>
+) -> napi::Result<bool> {
+  let public_inputs = public_inputs
+    .unwrap_or_default()
+    .into_iter()
+    .map(|input| prover::make_range_inputs::<Bls12_377>(input, field::ReduceMode::Strict))
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(to_napi_error)?;
+
+  verifier::verify_with_context::<Bls12_377>(
+    vk_file_path.as_str(),
+    proof_file_path.as_str(),
+    public_inputs,
+    &context
+  ).map_err(to_napi_error)
+}
+
+// finishes or rolls back an update interrupted by a crash
+// returns "clean", "rolled_back" or "completed"
+#[napi]
+pub fn recover_aggregated_commitment_update(
+  aggregated_commitment_file_path : String
+) -> napi::Result<String> {
+  let journal_path = prover::aggregated_commitment_journal_path(aggregated_commitment_file_path.as_str());
+  let recovery = transaction::recover(journal_path.as_str()).map_err(to_napi_error)?;
+  Ok(recovery.as_str().to_string())
+}
+
+#[napi]
+pub fn calculate_pedersen_commitment_bn128(
+  proving_file_path: String,
+  m : String,
+  v : String
+) -> napi::Result<String> {
+  let proving_key = keys::read_compressed_proving_key_from_file::<Bn254>(proving_file_path.as_str()).map_err(to_napi_error)?;
+  let m = prover::hex_string_to_scalar_field::<Bn254>(m).map_err(to_napi_error)?;
+  let v = prover::hex_string_to_scalar_field::<Bn254>(v).map_err(to_napi_error)?;
+
+  let commitment = prover::calculate_pedersen_commitment::<Bn254>(&proving_key.vk, &[m], v)
+    .map_err(to_napi_error)?;
+  Ok(format!("{:#?}", commitment))
+}
+
+// m : hex strings of the committed witnesses, in commitment order
+#[napi]
+pub fn calculate_vector_pedersen_commitment_bn128(
+  vk_file_path : String,
+  m : Vec<String>,
+  v : String
+) -> napi::Result<String> {
+  let vk = keys::read_verifying_key_from_file::<Bn254>(vk_file_path.as_str()).map_err(to_napi_error)?;
+  let m = m
+    .into_iter()
+    .map(prover::hex_string_to_scalar_field::<Bn254>)
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(to_napi_error)?;
+  let v = prover::hex_string_to_scalar_field::<Bn254>(v).map_err(to_napi_error)?;
+
+  let commitment = prover::calculate_pedersen_commitment::<Bn254>(&vk, &m, v)
+    .map_err(to_napi_error)?;
+  Ok(format!("{:#?}", commitment))
+}
+
+#[napi]
+pub fn calculate_vector_pedersen_commitment_bls12_381(
+  vk_file_path : String,
+  m : Vec<String>,
+  v : String
+) -> napi::Result<String> {
+  let vk = keys::read_verifying_key_from_file::<Bls12_381>(vk_file_path.as_str()).map_err(to_napi_error)?;
+  let m = m
+    .into_iter()
+    .map(prover::hex_string_to_scalar_field::<Bls12_381>)
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(to_napi_error)?;
+  let v = prover::hex_string_to_scalar_field::<Bls12_381>(v).map_err(to_napi_error)?;
+
+  let commitment = prover::calculate_pedersen_commitment::<Bls12_381>(&vk, &m, v)
+    .map_err(to_napi_error)?;
+  Ok(format!("{:#?}", commitment))
+}
+
+#[napi]
+pub fn calculate_vector_pedersen_commitment_bls12_377(
+  vk_file_path : String,
+  m : Vec<String>,
+  v : String
+) -> napi::Result<String> {
+  let vk = keys::read_verifying_key_from_file::<Bls12_377>(vk_file_path.as_str()).map_err(to_napi_error)?;
+  let m = m
+    .into_iter()
+    .map(prover::hex_string_to_scalar_field::<Bls12_377>)
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(to_napi_error)?;
+  let v = prover::hex_string_to_scalar_field::<Bls12_377>(v).map_err(to_napi_error)?;
+
+  let commitment = prover::calculate_pedersen_commitment::<Bls12_377>(&vk, &m, v)
+    .map_err(to_napi_error)?;
+  Ok(format!("{:#?}", commitment))
+}
+
+// true if proof.d opens to the opening key json
+#[napi]
+pub fn check_pedersen_commitment_bn128(
+  vk_file_path : String,
+  proof_file_path : String,
+  opening_key_file_path : String
+) -> napi::Result<bool> {
+  prover::check_pedersen_commitment::<Bn254>(
+    vk_file_path.as_str(),
+    proof_file_path.as_str(),
+    opening_key_file_path.as_str()
+  ).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn check_pedersen_commitment_bls12_381(
+  vk_file_path : String,
+  proof_file_path : String,
+  opening_key_file_path : String
+) -> napi::Result<bool> {
+  prover::check_pedersen_commitment::<Bls12_381>(
+    vk_file_path.as_str(),
+    proof_file_path.as_str(),
+    opening_key_file_path.as_str()
+  ).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn check_pedersen_commitment_bls12_377(
+  vk_file_path : String,
+  proof_file_path : String,
+  opening_key_file_path : String
+) -> napi::Result<bool> {
+  prover::check_pedersen_commitment::<Bls12_377>(
+    vk_file_path.as_str(),
+    proof_file_path.as_str(),
+    opening_key_file_path.as_str()
+  ).map_err(to_napi_error)
+}
+
+// circuit_dir : directory with the compiled range circuits (circom/range/build.sh)
+// key_dir     : directory to save range_<kind>_<n>_{pk,vk}.bin
+// bit_widths  : subset of 8, 16, 32, 64, 128, 252 (default: all)
+#[napi]
+pub fn setup_range_circuits_bn128(
+  circuit_dir : String,
+  key_dir : String,
+  seed : u32,
+  bit_widths : Option<Vec<u32>>
+) -> napi::Result<()> {
+  range::setup_range_circuits::<Bn254>(
+    circuit_dir.as_str(),
+    key_dir.as_str(),
+    &to_bit_widths(bit_widths),
+    seed as u64
+  ).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn setup_range_circuits_bls12_381(
+  circuit_dir : String,
+  key_dir : String,
+  seed : u32,
+  bit_widths : Option<Vec<u32>>
+) -> napi::Result<()> {
+  range::setup_range_circuits::<Bls12_381>(
+    circuit_dir.as_str(),
+    key_dir.as_str(),
+    &to_bit_widths(bit_widths),
+    seed as u64
+  ).map_err(to_napi_error)
+}
+
+// proves  min <= value <= max  with the smallest range_interval_<n> circuit
+// curve : "bn128" or "bls12-381", verify with verifyRangeBetweenBn128 / verifyRangeBetweenBls12381
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn prove_range(
+  curve : String,
+  circuit_dir : String,
+  key_dir : String,
+  proof_file_path : String,
+  value : String,
+  min : String,
+  max : String,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<()> {
+  let mode = field::ReduceMode::from_flag(reduce);
+  let opening_key = match curve.as_str() {
+    "bn128" => prove_range_between::<Bn254>(&circuit_dir, &key_dir, &proof_file_path, value, min, max, seed, mode),
+    "bls12-381" => prove_range_between::<Bls12_381>(&circuit_dir, &key_dir, &proof_file_path, value, min, max, seed, mode),
+    curve => Err(napi::Error::from_reason(format!("unsupported curve {}, expected bn128 or bls12-381", curve))),
+  }?;
+
+  write_opening_key(proof_file_path.as_str(), opening_key)?;
+  Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prove_range_between<E: ark_ec::pairing::Pairing>(
+  circuit_dir : &str,
+  key_dir : &str,
+  proof_file_path : &str,
+  value : String,
+  min : String,
+  max : String,
+  seed : u32,
+  mode : field::ReduceMode
+) -> napi::Result<String> {
+  range::prove_range::<E>(
+    circuit_dir,
+    key_dir,
+    proof_file_path,
+    prover::make_range_inputs::<E>(value, mode).map_err(to_napi_error)?,
+    prover::make_range_inputs::<E>(min, mode).map_err(to_napi_error)?,
+    prover::make_range_inputs::<E>(max, mode).map_err(to_napi_error)?,
+    seed as u64
+  ).map_err(to_napi_error)
+}
+
+// proves  0 <= value < 2^bit_width  with range_bits_<bit_width>
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn prove_range_bits_bn128(
+  circuit_dir : String,
+  key_dir : String,
+  proof_file_path : String,
+  value : String,
+  bit_width : u32,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<()> {
+  let opening_key = range::prove_range_bits::<Bn254>(
+    circuit_dir.as_str(),
+    key_dir.as_str(),
+    proof_file_path.as_str(),
+    prover::make_range_inputs::<Bn254>(value, field::ReduceMode::from_flag(reduce)).map_err(to_napi_error)?,
+    bit_width as usize,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  write_opening_key(proof_file_path.as_str(), opening_key)?;
+  Ok(())
+}
+
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn prove_range_bits_bls12_381(
+  circuit_dir : String,
+  key_dir : String,
+  proof_file_path : String,
+  value : String,
+  bit_width : u32,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<()> {
+  let opening_key = range::prove_range_bits::<Bls12_381>(
+    circuit_dir.as_str(),
+    key_dir.as_str(),
+    proof_file_path.as_str(),
+    prover::make_range_inputs::<Bls12_381>(value, field::ReduceMode::from_flag(reduce)).map_err(to_napi_error)?,
+    bit_width as usize,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  write_opening_key(proof_file_path.as_str(), opening_key)?;
+  Ok(())
+}
+
+#[napi]
+pub fn verify_range_bits_bn128(
+  key_dir : String,
+  proof_file_path : String,
+  bit_width : u32
+) -> napi::Result<bool> {
+  range::verify_range_bits::<Bn254>(key_dir.as_str(), proof_file_path.as_str(), bit_width as usize)
+    .map_err(to_napi_error)
+}
+
+#[napi]
+pub fn verify_range_bits_bls12_381(
+  key_dir : String,
+  proof_file_path : String,
+  bit_width : u32
+) -> napi::Result<bool> {
+  range::verify_range_bits::<Bls12_381>(key_dir.as_str(), proof_file_path.as_str(), bit_width as usize)
+    .map_err(to_napi_error)
+}
+
+#[napi]
+pub fn verify_range_between_bn128(
+  key_dir : String,
+  proof_file_path : String,
+  min : String,
+  max : String
+) -> napi::Result<bool> {
+  range::verify_range::<Bn254>(
+    key_dir.as_str(),
+    proof_file_path.as_str(),
+    prover::make_range_inputs::<Bn254>(min, field::ReduceMode::Strict).map_err(to_napi_error)?,
+    prover::make_range_inputs::<Bn254>(max, field::ReduceMode::Strict).map_err(to_napi_error)?
+  ).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn verify_range_between_bls12_381(
+  key_dir : String,
+  proof_file_path : String,
+  min : String,
+  max : String
+) -> napi::Result<bool> {
+  range::verify_range::<Bls12_381>(
+    key_dir.as_str(),
+    proof_file_path.as_str(),
+    prover::make_range_inputs::<Bls12_381>(min, field::ReduceMode::Strict).map_err(to_napi_error)?,
+    prover::make_range_inputs::<Bls12_381>(max, field::ReduceMode::Strict).map_err(to_napi_error)?
+  ).map_err(to_napi_error)
+}
+
+// proves every value in one proof, proof.d is a vector pedersen commitment
+// setup with commit_witness_count = values.length
+// opening key : {"m" : [hex, ...], "v" : hex}
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn prove_range_vector_bn128(
+  r1cs_file_path : String,
+  wasm_file_path : String,
+  pk_file_path : String,
+  proof_file_path : String,
+  values : Vec<String>,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<()> {
+  let mode = field::ReduceMode::from_flag(reduce);
+  let values = values
+    .into_iter()
+    .map(|value| prover::make_range_inputs::<Bn254>(value, mode))
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(to_napi_error)?;
+
+  let opening_key = range::prove_range_vector::<Bn254>(
+    r1cs_file_path.as_str(),
+    pk_file_path.as_str(),
+    wasm_file_path.as_str(),
+    proof_file_path.as_str(),
+    values,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  write_opening_key(proof_file_path.as_str(), opening_key)?;
+  Ok(())
+}
+
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn prove_range_vector_bls12_381(
+  r1cs_file_path : String,
+  wasm_file_path : String,
+  pk_file_path : String,
+  proof_file_path : String,
+  values : Vec<String>,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<()> {
+  let mode = field::ReduceMode::from_flag(reduce);
+  let values = values
+    .into_iter()
+    .map(|value| prover::make_range_inputs::<Bls12_381>(value, mode))
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(to_napi_error)?;
+
+  let opening_key = range::prove_range_vector::<Bls12_381>(
+    r1cs_file_path.as_str(),
+    pk_file_path.as_str(),
+    wasm_file_path.as_str(),
+    proof_file_path.as_str(),
+    values,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  write_opening_key(proof_file_path.as_str(), opening_key)?;
+  Ok(())
+}
+
+// committed witnesses named by circom signal (.sym file from `circom --sym`)
+// signals : committed signals in order, e.g. ["value"] or ["main.value"]
+// inputs  : circuit inputs by signal name, {"value" : ["42"]}
+// returns the opening key with the committed values by signal name, also saved as <proof>_opening_key.json
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn prove_signals_bn128(
+  r1cs_file_path : String,
+  wasm_file_path : String,
+  sym_file_path : String,
+  pk_file_path : String,
+  proof_file_path : String,
+  signals : Vec<String>,
+  inputs : HashMap<String, Vec<String>>,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<String> {
+  let inputs = to_circuit_inputs::<Bn254>(inputs, field::ReduceMode::from_flag(reduce))?;
+
+  let opening_key = prover::prove_signals::<Bn254, _>(
+    r1cs_file_path.as_str(),
+    pk_file_path.as_str(),
+    wasm_file_path.as_str(),
+    sym_file_path.as_str(),
+    proof_file_path.as_str(),
+    &signals,
+    inputs,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  write_opening_key(proof_file_path.as_str(), opening_key.clone())?;
+  Ok(opening_key)
+}
+
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn prove_signals_bls12_381(
+  r1cs_file_path : String,
+  wasm_file_path : String,
+  sym_file_path : String,
+  pk_file_path : String,
+  proof_file_path : String,
+  signals : Vec<String>,
+  inputs : HashMap<String, Vec<String>>,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<String> {
+  let inputs = to_circuit_inputs::<Bls12_381>(inputs, field::ReduceMode::from_flag(reduce))?;
+
+  let opening_key = prover::prove_signals::<Bls12_381, _>(
+    r1cs_file_path.as_str(),
+    pk_file_path.as_str(),
+    wasm_file_path.as_str(),
+    sym_file_path.as_str(),
+    proof_file_path.as_str(),
+    &signals,
+    inputs,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  write_opening_key(proof_file_path.as_str(), opening_key.clone())?;
+  Ok(opening_key)
+}
+
+// prove with the r1cs and wasm as buffers, the opening key is returned and saved as <proof>_opening_key.json
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn prove_from_buffers_bn128(
+  r1cs : Buffer,
+  wasm : Buffer,
+  pk_file_path : String,
+  proof_file_path : String,
+  commit_witness_count : u32,
+  inputs : HashMap<String, Vec<String>>,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<String> {
+  let inputs = to_circuit_inputs::<Bn254>(inputs, field::ReduceMode::from_flag(reduce))?;
+
+  let opening_key = prover::prove_from_bytes::<Bn254, _>(
+    &r1cs,
+    pk_file_path.as_str(),
+    &wasm,
+    proof_file_path.as_str(),
+    commit_witness_count as usize,
+    inputs,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  write_opening_key(proof_file_path.as_str(), opening_key.clone())?;
+  Ok(opening_key)
+}
+
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn prove_from_buffers_bls12_381(
+  r1cs : Buffer,
+  wasm : Buffer,
+  pk_file_path : String,
+  proof_file_path : String,
+  commit_witness_count : u32,
+  inputs : HashMap<String, Vec<String>>,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<String> {
+  let inputs = to_circuit_inputs::<Bls12_381>(inputs, field::ReduceMode::from_flag(reduce))?;
+
+  let opening_key = prover::prove_from_bytes::<Bls12_381, _>(
+    &r1cs,
+    pk_file_path.as_str(),
+    &wasm,
+    proof_file_path.as_str(),
+    commit_witness_count as usize,
+    inputs,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  write_opening_key(proof_file_path.as_str(), opening_key.clone())?;
+  Ok(opening_key)
+}
+
+// prove_from_buffers with the proving key as a buffer too, nothing is read or written
+#[napi]
+pub fn prove_from_key_buffers_bn128(
+  r1cs : Buffer,
+  wasm : Buffer,
+  pk : Buffer,
+  commit_witness_count : u32,
+  inputs : HashMap<String, Vec<String>>,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<ProofBuffer> {
+  let inputs = to_circuit_inputs::<Bn254>(inputs, field::ReduceMode::from_flag(reduce))?;
+
+  let (proof, opening_key) = prover::prove_from_key_bytes::<Bn254, _>(
+    &r1cs,
+    &pk,
+    &wasm,
+    commit_witness_count as usize,
+    inputs,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  Ok(ProofBuffer { proof : proof.into(), opening_key })
+}
+
+#[napi]
+pub fn prove_from_key_buffers_bls12_381(
+  r1cs : Buffer,
+  wasm : Buffer,
+  pk : Buffer,
+  commit_witness_count : u32,
+  inputs : HashMap<String, Vec<String>>,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<ProofBuffer> {
+  let inputs = to_circuit_inputs::<Bls12_381>(inputs, field::ReduceMode::from_flag(reduce))?;
+
+  let (proof, opening_key) = prover::prove_from_key_bytes::<Bls12_381, _>(
+    &r1cs,
+    &pk,
+    &wasm,
+    commit_witness_count as usize,
+    inputs,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  Ok(ProofBuffer { proof : proof.into(), opening_key })
+}
+
+// link generators hashed from a public label instead of the seed (nothing up my sleeve)
+// also writes the link keys, link_vk is what verify_link_generators checks
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn setup_with_hashed_link_generators_bn128(
+  r1cs_file_path : String,
+  commit_witness_count : u32,
+  seed : u32,
+  label : String,
+  path_pk : String,
+  path_vk : String,
+  path_link_pk : String,
+  path_link_vk : String,
+) -> napi::Result<()> {
+  setup::setup_from_circom_r1cs_with_link::<Bn254>(
+    r1cs_file_path.as_str(),
+    commit_witness_count as usize,
+    seed as u64,
+    &setup::LinkGenerators::Hashed(label.into_bytes()),
+    path_pk.as_str(),
+    path_vk.as_str(),
+    path_link_pk.as_str(),
+    path_link_vk.as_str()
+  ).map_err(to_napi_error)
+}
+
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn setup_with_hashed_link_generators_bls12_381(
+  r1cs_file_path : String,
+  commit_witness_count : u32,
+  seed : u32,
+  label : String,
+  path_pk : String,
+  path_vk : String,
+  path_link_pk : String,
+  path_link_vk : String,
+) -> napi::Result<()> {
+  setup::setup_from_circom_r1cs_with_link::<Bls12_381>(
+    r1cs_file_path.as_str(),
+    commit_witness_count as usize,
+    seed as u64,
+    &setup::LinkGenerators::Hashed(label.into_bytes()),
+    path_pk.as_str(),
+    path_vk.as_str(),
+    path_link_pk.as_str(),
+    path_link_vk.as_str()
+  ).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn verify_link_generators_bn128(
+  link_vk_file_path : String,
+  label : String,
+) -> napi::Result<bool> {
+  verifier::verify_link_generators_file::<Bn254>(link_vk_file_path.as_str(), label.as_bytes()).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn verify_link_generators_bls12_381(
+  link_vk_file_path : String,
+  label : String,
+) -> napi::Result<bool> {
+  verifier::verify_link_generators_file::<Bls12_381>(link_vk_file_path.as_str(), label.as_bytes()).map_err(to_napi_error)
+}
+
+// link generators given by the caller (fs.readFileSync of a generators file), e.g. the
+// published bases of another pedersen commitment system, see setup::link_generators_from_bytes
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn setup_with_link_generators_bn128(
+  r1cs_file_path : String,
+  commit_witness_count : u32,
+  seed : u32,
+  link_generators : Buffer,
+  path_pk : String,
+  path_vk : String,
+  path_link_pk : String,
+  path_link_vk : String,
+) -> napi::Result<()> {
+  let link_generators = setup::link_generators_from_bytes::<Bn254>(&link_generators).map_err(to_napi_error)?;
+  setup::setup_from_circom_r1cs_with_link::<Bn254>(
+    r1cs_file_path.as_str(),
+    commit_witness_count as usize,
+    seed as u64,
+    &setup::LinkGenerators::Provided(link_generators),
+    path_pk.as_str(),
+    path_vk.as_str(),
+    path_link_pk.as_str(),
+    path_link_vk.as_str()
+  ).map_err(to_napi_error)
+}
+
+#[napi]
+#[allow(clippy::too_many_arguments)]
+pub fn setup_with_link_generators_bls12_381(
+  r1cs_file_path : String,
+  commit_witness_count : u32,
+  seed : u32,
+  link_generators : Buffer,
+  path_pk : String,
+  path_vk : String,
+  path_link_pk : String,
+  path_link_vk : String,
+) -> napi::Result<()> {
+  let link_generators = setup::link_generators_from_bytes::<Bls12_381>(&link_generators).map_err(to_napi_error)?;
+  setup::setup_from_circom_r1cs_with_link::<Bls12_381>(
+    r1cs_file_path.as_str(),
+    commit_witness_count as usize,
+    seed as u64,
+    &setup::LinkGenerators::Provided(link_generators),
+    path_pk.as_str(),
+    path_vk.as_str(),
+    path_link_pk.as_str(),
+    path_link_vk.as_str()
+  ).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn verify_provided_link_generators_bn128(
+  link_vk_file_path : String,
+  link_generators : Buffer,
+) -> napi::Result<bool> {
+  let link_generators = setup::link_generators_from_bytes::<Bn254>(&link_generators).map_err(to_napi_error)?;
+  Ok(verifier::verify_provided_link_generators::<Bn254>(
+    &keys::read_link_verifying_key_from_file::<Bn254>(link_vk_file_path.as_str()).map_err(to_napi_error)?,
+    &link_generators
+  ))
+}
+
+#[napi]
+pub fn verify_provided_link_generators_bls12_381(
+  link_vk_file_path : String,
+  link_generators : Buffer,
+) -> napi::Result<bool> {
+  let link_generators = setup::link_generators_from_bytes::<Bls12_381>(&link_generators).map_err(to_napi_error)?;
+  Ok(verifier::verify_provided_link_generators::<Bls12_381>(
+    &keys::read_link_verifying_key_from_file::<Bls12_381>(link_vk_file_path.as_str()).map_err(to_napi_error)?,
+    &link_generators
+  ))
+}
+
+// native range circuit, no r1cs/wasm files needed
+// value_count values of bit_width bits per proof (commit_witness_count = value_count)
+#[napi]
+pub fn setup_native_range_bn128(
+  bit_width : u32,
+  value_count : u32,
+  seed : u32,
+  pk_file_path : String,
+  vk_file_path : String
+) -> napi::Result<()> {
+  range::setup_native_range::<Bn254>(
+    bit_width as usize,
+    value_count as usize,
+    seed as u64,
+    pk_file_path.as_str(),
+    vk_file_path.as_str()
+  ).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn setup_native_range_bls12_381(
+  bit_width : u32,
+  value_count : u32,
+  seed : u32,
+  pk_file_path : String,
+  vk_file_path : String
+) -> napi::Result<()> {
+  range::setup_native_range::<Bls12_381>(
+    bit_width as usize,
+    value_count as usize,
+    seed as u64,
+    pk_file_path.as_str(),
+    vk_file_path.as_str()
+  ).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn setup_native_range_bls12_377(
+  bit_width : u32,
+  value_count : u32,
+  seed : u32,
+  pk_file_path : String,
+  vk_file_path : String
+) -> napi::Result<()> {
+  range::setup_native_range::<Bls12_377>(
+    bit_width as usize,
+    value_count as usize,
+    seed as u64,
+    pk_file_path.as_str(),
+    vk_file_path.as_str()
+  ).map_err(to_napi_error)
+}
+
+// verify with verifyRangeBn128 / verifyRangeBls12381 / verifyRangeBls12377, the proof has no public inputs
+#[napi]
+pub fn prove_native_range_bn128(
+  pk_file_path : String,
+  proof_file_path : String,
+  bit_width : u32,
+  values : Vec<String>,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<()> {
+  let mode = field::ReduceMode::from_flag(reduce);
+  let values = values
+    .into_iter()
+    .map(|value| prover::make_range_inputs::<Bn254>(value, mode))
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(to_napi_error)?;
+
+  let opening_key = range::prove_native_range::<Bn254>(
+    pk_file_path.as_str(),
+    proof_file_path.as_str(),
+    bit_width as usize,
+    &values,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  write_opening_key(proof_file_path.as_str(), opening_key)?;
+  Ok(())
+}
+
+#[napi]
+pub fn prove_native_range_bls12_381(
+  pk_file_path : String,
+  proof_file_path : String,
+  bit_width : u32,
+  values : Vec<String>,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<()> {
+  let mode = field::ReduceMode::from_flag(reduce);
+  let values = values
+    .into_iter()
+    .map(|value| prover::make_range_inputs::<Bls12_381>(value, mode))
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(to_napi_error)?;
+
+  let opening_key = range::prove_native_range::<Bls12_381>(
+    pk_file_path.as_str(),
+    proof_file_path.as_str(),
+    bit_width as usize,
+    &values,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  write_opening_key(proof_file_path.as_str(), opening_key)?;
+  Ok(())
+}
+
+#[napi]
+pub fn prove_native_range_bls12_377(
+  pk_file_path : String,
+  proof_file_path : String,
+  bit_width : u32,
+  values : Vec<String>,
+  seed : u32,
+  reduce : Option<bool>
+) -> napi::Result<()> {
+  let mode = field::ReduceMode::from_flag(reduce);
+  let values = values
+    .into_iter()
+    .map(|value| prover::make_range_inputs::<Bls12_377>(value, mode))
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(to_napi_error)?;
+
+  let opening_key = range::prove_native_range::<Bls12_377>(
+    pk_file_path.as_str(),
+    proof_file_path.as_str(),
+    bit_width as usize,
+    &values,
+    seed as u64
+  ).map_err(to_napi_error)?;
+
+  write_opening_key(proof_file_path.as_str(), opening_key)?;
+  Ok(())
+}
+
+// returns the canonical big-endian hex encoding used by the opening key files
+// value : decimal ("1234", "-1") or hex ("0x04d2")
+#[napi]
+pub fn parse_field_element_bn128(
+  value : String,
+  reduce : Option<bool>
+) -> napi::Result<String> {
+  let element = field::parse_field_element::<ark_bn254::Fr>(
+    value.as_str(),
+    field::ReduceMode::from_flag(reduce)
+  ).map_err(to_napi_error)?;
+  Ok(field::field_element_to_hex(&element))
+}
+
+#[napi]
+pub fn parse_field_element_bls12_381(
+  value : String,
+  reduce : Option<bool>
+) -> napi::Result<String> {
+  let element = field::parse_field_element::<ark_bls12_381::Fr>(
+    value.as_str(),
+    field::ReduceMode::from_flag(reduce)
+  ).map_err(to_napi_error)?;
+  Ok(field::field_element_to_hex(&element))
+}
+
+#[napi]
+pub fn parse_field_element_bls12_377(
+  value : String,
+  reduce : Option<bool>
+) -> napi::Result<String> {
+  let element = field::parse_field_element::<ark_bls12_377::Fr>(
+    value.as_str(),
+    field::ReduceMode::from_flag(reduce)
+  ).map_err(to_napi_error)?;
+  Ok(field::field_element_to_hex(&element))
+}
+
+// bytes are big-endian unless little_endian is set
+#[napi]
+pub fn field_element_from_bytes_bn128(
+  bytes : Buffer,
+  little_endian : Option<bool>,
+  reduce : Option<bool>
+) -> napi::Result<String> {
+  let element = field::field_element_from_bytes::<ark_bn254::Fr>(
+    &bytes,
+    to_endianness(little_endian),
+    field::ReduceMode::from_flag(reduce)
+  ).map_err(to_napi_error)?;
+  Ok(field::field_element_to_hex(&element))
+}
+
+#[napi]
+pub fn field_element_from_bytes_bls12_381(
+  bytes : Buffer,
+  little_endian : Option<bool>,
+  reduce : Option<bool>
+) -> napi::Result<String> {
+  let element = field::field_element_from_bytes::<ark_bls12_381::Fr>(
+    &bytes,
+    to_endianness(little_endian),
+    field::ReduceMode::from_flag(reduce)
+  ).map_err(to_napi_error)?;
+  Ok(field::field_element_to_hex(&element))
+}
+
+#[napi]
+pub fn field_element_from_bytes_bls12_377(
+  bytes : Buffer,
+  little_endian : Option<bool>,
+  reduce : Option<bool>
+) -> napi::Result<String> {
+  let element = field::field_element_from_bytes::<ark_bls12_377::Fr>(
+    &bytes,
+    to_endianness(little_endian),
+    field::ReduceMode::from_flag(reduce)
+  ).map_err(to_napi_error)?;
+  Ok(field::field_element_to_hex(&element))
+}
+
+fn to_endianness(little_endian : Option<bool>) -> field::Endianness {
+  match little_endian {
+    Some(true) => field::Endianness::Little,
+    _ => field::Endianness::Big
+  }
+}
+
+// js classes for homomorphic commitment arithmetic, one pair per curve
+//   commitment  : proof.d or an aggregated commitment
+//   opening key : {m, v} of the commitment
+// commitment.add(other) opens to openingKey.add(otherOpeningKey), same for sub, negate, mul
+macro_rules! commitment_classes {
+  ($commitment:ident, $opening_key:ident, $curve:ty) => {
+    #[napi]
+    pub struct $commitment {
+      inner : commitment::Commitment<$curve>
+    }
+
+    #[napi]
+    impl $commitment {
+      #[napi(factory)]
+      pub fn from_proof_file(proof_file_path : String) -> napi::Result<Self> {
+        let inner = prover::read_proof_commitment::<$curve>(proof_file_path.as_str()).map_err(to_napi_error)?;
+        Ok(Self { inner })
+      }
+
+      #[napi(factory)]
+      pub fn from_file(path : String) -> napi::Result<Self> {
+        let inner = commitment::Commitment::<$curve>::read_from_file(path.as_str())
+          .map_err(to_napi_error)?;
+        Ok(Self { inner })
+      }
+
+      #[napi(factory)]
+      pub fn from_buffer(bytes : Buffer) -> napi::Result<Self> {
+        let inner = commitment::Commitment::<$curve>::from_bytes(&bytes)
+          .map_err(to_napi_error)?;
+        Ok(Self { inner })
+      }
+
+      #[napi(factory)]
+      pub fn from_opening_key(vk_file_path : String, opening_key : &$opening_key) -> napi::Result<Self> {
+        let vk = keys::read_verifying_key_from_file::<$curve>(vk_file_path.as_str()).map_err(to_napi_error)?;
+        let inner = opening_key.inner.commit(&vk).map_err(to_napi_error)?;
+        Ok(Self { inner })
+      }
+
+      #[napi]
+      pub fn add(&self, other : &$commitment) -> Self {
+        Self { inner : self.inner.add(&other.inner) }
+      }
+
+      #[napi]
+      pub fn sub(&self, other : &$commitment) -> Self {
+        Self { inner : self.inner.sub(&other.inner) }
+      }
+
+      #[napi]
+      pub fn negate(&self) -> Self {
+        Self { inner : self.inner.neg() }
+      }
+
+      // scalar : decimal or 0x hex, negative values allowed
+      #[napi]
+      pub fn mul(&self, scalar : String) -> napi::Result<Self> {
+        let scalar = field::parse_field_element(scalar.as_str(), field::ReduceMode::Strict)
+          .map_err(to_napi_error)?;
+        Ok(Self { inner : self.inner.mul(&scalar) })
+      }
+
+      #[napi]
+      pub fn equals(&self, other : &$commitment) -> bool {
+        self.inner == other.inner
+      }
+
+      #[napi]
+      pub fn to_buffer(&self) -> Buffer {
+        self.inner.to_bytes().into()
+      }
+
+      #[napi]
+      pub fn save(&self, path : String) -> napi::Result<()> {
+        self.inner.write_to_file(path.as_str()).map_err(to_napi_error)
+      }
+
+      #[napi(js_name = "toString")]
+      pub fn describe(&self) -> String {
+        format!("{:?}", self.inner)
+      }
+    }
+
+    #[napi]
+    pub struct $opening_key {
+      inner : commitment::OpeningKey<$curve>
+    }
+
+    #[napi]
+    impl $opening_key {
+      // m, v : hex strings as in the opening key json files
+      #[napi(constructor)]
+      pub fn new(m : Vec<String>, v : String) -> napi::Result<Self> {
+        let m = m
+          .iter()
+          .map(|m| field::parse_hex_field_element(m.as_str(), field::ReduceMode::Strict))
+          .collect::<Result<Vec<_>, _>>()
+          .map_err(to_napi_error)?;
+        let v = field::parse_hex_field_element(v.as_str(), field::ReduceMode::Strict)
+          .map_err(to_napi_error)?;
+        Ok(Self { inner : commitment::OpeningKey::new(m, v) })
+      }
+
+      #[napi(factory)]
+      pub fn from_json(json : String) -> napi::Result<Self> {
+        let inner = commitment::OpeningKey::<$curve>::from_json(json.as_str())
+          .map_err(to_napi_error)?;
+        Ok(Self { inner })
+      }
+
+      #[napi(factory)]
+      pub fn from_file(path : String) -> napi::Result<Self> {
+        let inner = commitment::OpeningKey::<$curve>::read_from_file(path.as_str())
+          .map_err(to_napi_error)?;
+        Ok(Self { inner })
+      }
+
+      #[napi(getter)]
+      pub fn m(&self) -> Vec<String> {
+        self.inner.m.iter().map(field::field_element_to_hex).collect()
+      }
+
+      #[napi(getter)]
+      pub fn v(&self) -> String {
+        field::field_element_to_hex(&self.inner.v)
+      }
+
+      #[napi]
+      pub fn add(&self, other : &$opening_key) -> napi::Result<Self> {
+        let inner = self.inner.add(&other.inner).map_err(to_napi_error)?;
+        Ok(Self { inner })
+      }
+
+      #[napi]
+      pub fn sub(&self, other : &$opening_key) -> napi::Result<Self> {
+        let inner = self.inner.sub(&other.inner).map_err(to_napi_error)?;
+        Ok(Self { inner })
+      }
+
+      #[napi]
+      pub fn negate(&self) -> Self {
+        Self { inner : self.inner.neg() }
+      }
+
+      #[napi]
+      pub fn mul(&self, scalar : String) -> napi::Result<Self> {
+        let scalar = field::parse_field_element(scalar.as_str(), field::ReduceMode::Strict)
+          .map_err(to_napi_error)?;
+        Ok(Self { inner : self.inner.mul(&scalar) })
+      }
+
+      #[napi]
+      pub fn equals(&self, other : &$opening_key) -> bool {
+        self.inner == other.inner
+      }
+
+      #[napi]
+      pub fn to_json(&self) -> String {
+        self.inner.to_json()
+      }
+
+      #[napi]
+      pub fn save(&self, path : String) -> napi::Result<()> {
+        self.inner.write_to_file(path.as_str()).map_err(to_napi_error)
+      }
+    }
+  };
+}
+
+commitment_classes!(PedersenCommitmentBn128, OpeningKeyBn128, Bn254);
+
+commitment_classes!(PedersenCommitmentBls12381, OpeningKeyBls12381, Bls12_381);
+
+commitment_classes!(PedersenCommitmentBls12377, OpeningKeyBls12377, Bls12_377);
+
+// js class for the aggregate ledger, one per curve
+// members are added from their proof file and (optionally) opening key file
+macro_rules! ledger_class {
+  ($ledger:ident, $commitment:ident, $opening_key:ident, $curve:ty) => {
+    #[napi]
+    pub struct $ledger {
+      inner : ledger::AggregateLedger<$curve>
+    }
+
+    #[napi]
+    impl $ledger {
+      #[napi(constructor)]
+      #[allow(clippy::new_without_default)]
+      pub fn new() -> Self {
+        Self { inner : ledger::AggregateLedger::new() }
+      }
+
+      #[napi(factory)]
+      pub fn load(path : String) -> napi::Result<Self> {
+        let inner = ledger::AggregateLedger::<$curve>::read_from_file(path.as_str())
+          .map_err(to_napi_error)?;
+        Ok(Self { inner })
+      }
+
+      #[napi]
+      pub fn save(&self, path : String) -> napi::Result<()> {
+        self.inner.write_to_file(path.as_str()).map_err(to_napi_error)
+      }
+
+      #[napi]
+      pub fn add_member(
+        &mut self,
+        id : String,
+        proof_file_path : String,
+        opening_key_file_path : Option<String>
+      ) -> napi::Result<()> {
+        let (proof, opening_key) = read_member::<$curve>(proof_file_path, opening_key_file_path)?;
+        self.inner.add_member(id.as_str(), proof, opening_key).map_err(to_napi_error)
+      }
+
+      #[napi]
+      pub fn remove_member(&mut self, id : String) -> napi::Result<()> {
+        self.inner.remove_member(id.as_str()).map_err(to_napi_error)?;
+        Ok(())
+      }
+
+      #[napi]
+      pub fn update_member(
+        &mut self,
+        id : String,
+        proof_file_path : String,
+        opening_key_file_path : Option<String>
+      ) -> napi::Result<()> {
+        let (proof, opening_key) = read_member::<$curve>(proof_file_path, opening_key_file_path)?;
+        self.inner.update_member(id.as_str(), proof, opening_key).map_err(to_napi_error)?;
+        Ok(())
+      }
+
+      #[napi]
+      pub fn member_ids(&self) -> Vec<String> {
+        self.inner.member_ids()
+      }
+
+      #[napi]
+      pub fn member_commitment(&self, id : String) -> Option<$commitment> {
+        self.inner.member(id.as_str()).map(|member| $commitment { inner : member.commitment() })
+      }
+
+      #[napi]
+      pub fn aggregate(&self) -> $commitment {
+        $commitment { inner : self.inner.aggregate() }
+      }
+
+      #[napi]
+      pub fn recompute_aggregate(&self) -> $commitment {
+        $commitment { inner : self.inner.recompute_aggregate() }
+      }
+
+      #[napi]
+      pub fn aggregate_opening_key(&self) -> napi::Result<Option<$opening_key>> {
+        let opening_key = self.inner.aggregate_opening_key().map_err(to_napi_error)?;
+        Ok(opening_key.map(|inner| $opening_key { inner }))
+      }
+
+      // vk_file_path : also check the opening keys of the members
+      #[napi]
+      pub fn check(&self, vk_file_path : Option<String>) -> napi::Result<bool> {
+        let vk = vk_file_path
+          .map(|path| keys::read_verifying_key_from_file::<$curve>(path.as_str()))
+          .transpose()
+          .map_err(to_napi_error)?;
+        self.inner.check(vk.as_ref()).map_err(to_napi_error)
+      }
+
+      #[napi]
+      pub fn repair(&mut self) {
+        self.inner.repair()
+      }
+    }
+  };
+}
+
+ledger_class!(AggregateLedgerBn128, PedersenCommitmentBn128, OpeningKeyBn128, Bn254);
+
+ledger_class!(AggregateLedgerBls12381, PedersenCommitmentBls12381, OpeningKeyBls12381, Bls12_381);
+
+ledger_class!(AggregateLedgerBls12377, PedersenCommitmentBls12377, OpeningKeyBls12377, Bls12_377);
+
+// js class for the proof of knowledge of a commitment opening, one per curve
+// context : optional bytes the proof is bound to (e.g. a nonce of the claim), must match on verify
+macro_rules! opening_proof_class {
+  ($proof:ident, $commitment:ident, $opening_key:ident, $curve:ty) => {
+    #[napi]
+    pub struct $proof {
+      inner : sigma::SchnorrProof<<$curve as ark_ec::pairing::Pairing>::G1Affine>
+    }
+
+    #[napi]
+    impl $proof {
+      #[napi(factory)]
+      pub fn prove(
+        vk_file_path : String,
+        opening_key : &$opening_key,
+        context : Option<Buffer>
+      ) -> napi::Result<Self> {
+        let vk = keys::read_verifying_key_from_file::<$curve>(vk_file_path.as_str()).map_err(to_napi_error)?;
+        let inner = prover::prove_opening::<$curve>(&vk, &opening_key.inner, context.as_deref().unwrap_or(&[]))
+          .map_err(to_napi_error)?;
+        Ok(Self { inner })
+      }
+
+      #[napi(factory)]
+      pub fn from_buffer(bytes : Buffer) -> napi::Result<Self> {
+        let inner = sigma::SchnorrProof::from_bytes(&bytes).map_err(to_napi_error)?;
+        Ok(Self { inner })
+      }
+
+      #[napi(factory)]
+      pub fn from_json(json : String) -> napi::Result<Self> {
+        let inner = sigma::SchnorrProof::from_json(json.as_str()).map_err(to_napi_error)?;
+        Ok(Self { inner })
+      }
+
+      // commitment : proof.d (PedersenCommitment.fromProofFile) or an aggregated commitment
+      #[napi]
+      pub fn verify(
+        &self,
+        vk_file_path : String,
+        commitment : &$commitment,
+        context : Option<Buffer>
+      ) -> napi::Result<bool> {
+        let vk = keys::read_verifying_key_from_file::<$curve>(vk_file_path.as_str()).map_err(to_napi_error)?;
+        Ok(verifier::verify_opening::<$curve>(&vk, &commitment.inner, &self.inner, context.as_deref().unwrap_or(&[])))
+      }
+
+      #[napi]
+      pub fn to_buffer(&self) -> Buffer {
+        self.inner.to_bytes().into()
+      }
+
+      #[napi]
+      pub fn to_json(&self) -> String {
+        self.inner.to_json()
+      }
+    }
+  };
+}
+
+opening_proof_class!(OpeningProofBn128, PedersenCommitmentBn128, OpeningKeyBn128, Bn254);
+
+opening_proof_class!(OpeningProofBls12381, PedersenCommitmentBls12381, OpeningKeyBls12381, Bls12_381);
+
+opening_proof_class!(OpeningProofBls12377, PedersenCommitmentBls12377, OpeningKeyBls12377, Bls12_377);
+
+fn read_member<E: ark_ec::pairing::Pairing>(
+  proof_file_path : String,
+  opening_key_file_path : Option<String>
+) -> napi::Result<(legogroth16::Proof<E>, Option<commitment::OpeningKey<E>>)> {
+  let proof = prover::read_proof_from_file::<E>(proof_file_path.as_str()).map_err(to_napi_error)?;
+  let opening_key = match opening_key_file_path {
+    Some(path) => Some(commitment::OpeningKey::<E>::read_from_file(path.as_str()).map_err(to_napi_error)?),
+    None => None
+  };
+  Ok((proof, opening_key))
+}
+
+fn to_pedersen_bases<E: ark_ec::pairing::Pairing>(
+  g : Vec<String>,
+  h : String
+) -> napi::Result<sigma::PedersenBases<E::G1Affine>> {
+  let g = g
+    .into_iter()
+    .map(hex_to_g1::<E>)
+    .collect::<napi::Result<Vec<_>>>()?;
+  Ok(sigma::PedersenBases::new(g, hex_to_g1::<E>(h)?))
+}
+
+// hex of a compressed g1 point, same encoding as PedersenCommitment.toBuffer()
+fn hex_to_g1<E: ark_ec::pairing::Pairing>(point : String) -> napi::Result<E::G1Affine> {
+  let bytes = hex::decode(point.trim_start_matches("0x")).map_err(to_napi_error)?;
+  E::G1Affine::deserialize_compressed(&*bytes).map_err(to_napi_error)
+}
+
+// no public inputs for every proof if omitted
+fn to_public_inputs<E: ark_ec::pairing::Pairing>(
+  public_inputs : Option<Vec<Vec<String>>>,
+  proof_count : usize
+) -> napi::Result<Vec<Vec<E::ScalarField>>> {
+  match public_inputs {
+    Some(public_inputs) => public_inputs
+      .into_iter()
+      .map(|inputs| inputs
+        .into_iter()
+        .map(|input| prover::make_range_inputs::<E>(input, field::ReduceMode::Strict))
+        .collect::<Result<Vec<_>, _>>())
+      .collect::<Result<Vec<_>, _>>()
+      .map_err(to_napi_error),
+    None => Ok(vec![Vec::new(); proof_count])
+  }
+}
+
+fn to_inputs<E: ark_ec::pairing::Pairing>(
+  inputs : Option<Vec<String>>
+) -> napi::Result<Vec<E::ScalarField>> {
+  inputs
+    .unwrap_or_default()
+    .into_iter()
+    .map(|input| prover::make_range_inputs::<E>(input, field::ReduceMode::Strict))
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(to_napi_error)
+}
+
+fn to_bit_widths(bit_widths : Option<Vec<u32>>) -> Vec<usize> {
+  match bit_widths {
+    Some(bit_widths) => bit_widths.into_iter().map(|n| n as usize).collect(),
+    None => range::RANGE_BIT_WIDTHS.to_vec()
+  }
+}
+
+// proof.bin -> proof_opening_key.json
+fn write_opening_key(proof_file_path : &str, opening_key : String) -> napi::Result<()> {
+  let opening_key_path = format!("{}{}", proof_file_path.trim_end_matches(".bin"), "_opening_key.json");
+  write(
+    opening_key_path.as_str(),
+    opening_key.as_bytes()
+  ).map_err(to_napi_error)
+}
+
+fn to_circuit_inputs<E:ark_ec::pairing::Pairing>(
+  inputs : HashMap<String, Vec<String>>,
+  mode : field::ReduceMode
+) -> napi::Result<HashMap<String, Vec<E::ScalarField>>> {
+  inputs
+    .into_iter()
+    .map(|(name, values)| {
+      let values = values
+        .into_iter()
+        .map(|value| prover::make_range_inputs::<E>(value, mode))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(to_napi_error)?;
+      Ok((name, values))
+    })
+    .collect()
+}
+
+fn to_napi_error<T: std::fmt::Display>(e : T) -> napi::Error {
+  napi::Error::from_reason(e.to_string())
+}
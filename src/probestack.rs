@@ -0,0 +1,70 @@
+// __rust_probestack for the wasmer witness calculator
+//
+// wasmer-vm 2.3 calls __rust_probestack in the prologue of the compiled wasm functions on x86_64,
+// recent rust toolchains no longer export it from compiler-builtins (functions use inline stack probes),
+// so every binary that runs a circom wasm fails to link without it.
+// same code as rust-lang/compiler-builtins src/probestack.rs (x86_64), defined weak so that
+// the compiler-builtins one wins on the toolchains that still have it
+//
+// in : rax = size of the frame, probes every page of it, all registers are preserved
+
+// the caller checked that more than a page is needed (or allocates dynamically, hence the first check)
+// 8(%rsp) accounts for the return address pushed by the call
+#[cfg(target_arch = "x86_64")]
+macro_rules! probestack_body {
+    () => {
+        "
+        .cfi_startproc
+        pushq  %rbp
+        .cfi_adjust_cfa_offset 8
+        .cfi_offset %rbp, -16
+        movq   %rsp, %rbp
+        .cfi_def_cfa_register %rbp
+
+        mov    %rax, %r11
+
+        cmp    $0x1000, %r11
+        jna    3f
+    2:
+        sub    $0x1000, %rsp
+        test   %rsp, 8(%rsp)
+        sub    $0x1000, %r11
+        cmp    $0x1000, %r11
+        ja     2b
+
+    3:
+        sub    %r11, %rsp
+        test   %rsp, 8(%rsp)
+
+        add    %rax, %rsp
+
+        leave
+        .cfi_def_cfa_register %rsp
+        .cfi_adjust_cfa_offset -8
+        ret
+        .cfi_endproc
+        "
+    };
+}
+
+#[cfg(all(target_arch = "x86_64", target_os = "linux"))]
+core::arch::global_asm!(
+    ".pushsection .text.__rust_probestack,\"ax\",@progbits",
+    ".weak __rust_probestack",
+    ".hidden __rust_probestack",
+    ".type __rust_probestack,@function",
+    "__rust_probestack:",
+    probestack_body!(),
+    ".size __rust_probestack, . - __rust_probestack",
+    ".popsection",
+    options(att_syntax)
+);
+
+#[cfg(all(target_arch = "x86_64", target_os = "macos"))]
+core::arch::global_asm!(
+    ".weak_definition ___rust_probestack",
+    ".private_extern ___rust_probestack",
+    "___rust_probestack:",
+    probestack_body!(),
+    options(att_syntax)
+);
@@ -1,5 +1,5 @@
-use ark_ec::{pairing::{Pairing}, AffineRepr};
-use ark_ff::{PrimeField,BigInteger };
+use ark_ec::{pairing::Pairing, CurveGroup};
+use ark_ff::{Field, PrimeField};
 use ark_std::{
     rand::
     {
@@ -7,6 +7,7 @@ use ark_std::{
         SeedableRng
     },
     UniformRand,
+    Zero,
 };
 use ark_serialize::{
     CanonicalSerialize, 
@@ -14,22 +15,38 @@ use ark_serialize::{
 };
 use legogroth16::{
     ProvingKey,
+    VerifyingKey,
     circom::{
         circuit::CircomCircuit,
         witness::WitnessCalculator
-    }, create_random_proof, Proof,
+    }, create_random_proof, Proof, prepare_verifying_key, verify_proof, rerandomize_proof_1,
 };
+use num_bigint::BigUint;
 use std::{
     fs::{write, read},
     collections::HashMap,
     time::SystemTime
 };
-use hex::ToHex;
 
-
-use crate::keys::{read_compressed_proving_key_from_file, abs_path};
+use crate::circuit::{check_bit_width, circom_circuit_from_r1cs_bytes, CircomCommittedCircuit, CommittedCircuit};
+use crate::keys::{proving_key_from_bytes, read_compressed_proving_key_from_file, read_verifying_key_from_file};
+use crate::error::Error;
+use crate::commitment::{Commitment, OpeningKey};
+use crate::transaction::{self, FileTransaction};
+use crate::inputs::check_inputs;
+use crate::sym::{SignalOpening, SymbolTable};
+use crate::sigma::{
+    EqualityProof, PedersenBases, SchnorrProof, AGGREGATED_TOTAL_LABEL, COMMITMENT_EQUALITY_LABEL, OPENING_LABEL
+};
+use crate::field::{
+    parse_field_element,
+    parse_hex_field_element,
+    FieldParseError,
+    ReduceMode
+};
 
 // return pedersen commitment opening keys (m, v)
+// the inputs are checked against the input signals of the wasm first, see inputs::check_inputs
 pub fn prove<
     E: Pairing,
     I: IntoIterator<Item = (String, Vec<E::ScalarField>)>
@@ -41,91 +58,326 @@ pub fn prove<
     commit_witness_count : usize,
     inputs : I,
     seed : u64,
-)  -> String {
-    let mut circuit: CircomCircuit<E> = CircomCircuit::<E>::from_r1cs_file(abs_path(r1cs_file_path)).unwrap();
+)  -> Result<String, Error> {
+    let (proof, opening_key) = create_proof::<E, I>(
+        r1cs_file_path,
+        key_file_path,
+        wasm_file_path,
+        commit_witness_count,
+        inputs,
+        seed
+    )?;
+
+    write(
+        proof_file_path,
+        proof_to_bytes(&proof)
+    )?;
+
+    Ok(opening_key.to_json())
+}
+
+// same as prove, but the proof is returned instead of written
+pub fn create_proof<
+    E: Pairing,
+    I: IntoIterator<Item = (String, Vec<E::ScalarField>)>
+> (
+    r1cs_file_path : &str,
+    key_file_path : &str,
+    wasm_file_path : &str,
+    commit_witness_count : usize,
+    inputs : I,
+    seed : u64,
+) -> Result<(Proof<E>, OpeningKey<E>), Error> {
+    create_proof_with_blinding::<E, I>(
+        r1cs_file_path,
+        key_file_path,
+        wasm_file_path,
+        commit_witness_count,
+        inputs,
+        None,
+        seed
+    )
+}
+
+// v : blinding of proof.d, random if None
+pub fn create_proof_with_blinding<
+    E: Pairing,
+    I: IntoIterator<Item = (String, Vec<E::ScalarField>)>
+> (
+    r1cs_file_path : &str,
+    key_file_path : &str,
+    wasm_file_path : &str,
+    commit_witness_count : usize,
+    inputs : I,
+    v : Option<E::ScalarField>,
+    seed : u64,
+) -> Result<(Proof<E>, OpeningKey<E>), Error> {
+    let circuit = circom_circuit::<E, I>(r1cs_file_path, wasm_file_path, None, inputs)?;
 
     let proving_key:ProvingKey<E> = read_compressed_proving_key_from_file::<E>(
         key_file_path
-    );
-    let mut wits_calc = WitnessCalculator::<E>::from_wasm_file(wasm_file_path).unwrap();
-    let all_wires = wits_calc.calculate_witnesses::<I>(inputs, true).unwrap();
+    )?;
+
+    prove_circuit::<E, _>(
+        CircomCommittedCircuit { circuit, commit_witness_count },
+        &proving_key,
+        v,
+        seed
+    )
+}
+
+// prove with the r1cs and wasm already in memory (embedded, fetched, integrity-checked)
+// same proof and opening key as prove, only the key and proof are files
+pub fn prove_from_bytes<
+    E: Pairing,
+    I: IntoIterator<Item = (String, Vec<E::ScalarField>)>
+> (
+    r1cs_bytes : &[u8],
+    key_file_path : &str,
+    wasm_bytes : &[u8],
+    proof_file_path: &str,
+    commit_witness_count : usize,
+    inputs : I,
+    seed : u64,
+) -> Result<String, Error> {
+    let proving_key:ProvingKey<E> = read_compressed_proving_key_from_file::<E>(
+        key_file_path
+    )?;
+    let (proof, opening_key) = create_proof_from_bytes::<E, I>(
+        r1cs_bytes,
+        wasm_bytes,
+        &proving_key,
+        commit_witness_count,
+        inputs,
+        seed
+    )?;
+
+    write(
+        proof_file_path,
+        proof_to_bytes(&proof)
+    )?;
+
+    Ok(opening_key.to_json())
+}
+
+// prove without any file : r1cs, wasm and proving key as bytes (keys::compressed_proving_key_bytes)
+// return the proof bytes (the content of a proof file) and the opening key json
+pub fn prove_from_key_bytes<
+    E: Pairing,
+    I: IntoIterator<Item = (String, Vec<E::ScalarField>)>
+> (
+    r1cs_bytes : &[u8],
+    pk_bytes : &[u8],
+    wasm_bytes : &[u8],
+    commit_witness_count : usize,
+    inputs : I,
+    seed : u64,
+) -> Result<(Vec<u8>, String), Error> {
+    let proving_key = proving_key_from_bytes::<E>(pk_bytes)?;
+    let (proof, opening_key) = create_proof_from_bytes::<E, I>(
+        r1cs_bytes,
+        wasm_bytes,
+        &proving_key,
+        commit_witness_count,
+        inputs,
+        seed
+    )?;
+
+    Ok((proof_to_bytes(&proof), opening_key.to_json()))
+}
+
+// create_proof without any file
+pub fn create_proof_from_bytes<
+    E: Pairing,
+    I: IntoIterator<Item = (String, Vec<E::ScalarField>)>
+> (
+    r1cs_bytes : &[u8],
+    wasm_bytes : &[u8],
+    proving_key : &ProvingKey<E>,
+    commit_witness_count : usize,
+    inputs : I,
+    seed : u64,
+) -> Result<(Proof<E>, OpeningKey<E>), Error> {
+    let circuit = set_circom_wires::<E, I>(
+        circom_circuit_from_r1cs_bytes::<E>(r1cs_bytes)?,
+        WitnessCalculator::<E>::from_wasm_bytes(wasm_bytes)?,
+        None,
+        inputs
+    )?;
+
+    prove_circuit::<E, _>(
+        CircomCommittedCircuit { circuit, commit_witness_count },
+        proving_key,
+        None,
+        seed
+    )
+}
+
+// prove with the committed witnesses named by their circom signals
+// sym_file_path : .sym file of the circuit (`circom --sym`)
+// signals       : committed signals in order, with or without the "main." prefix
+// they are checked against the committed positions of the proving key before proving
+// return the opening key with the committed values by signal name, see sym::SignalOpening
+#[allow(clippy::too_many_arguments)]
+pub fn prove_signals<
+    E: Pairing,
+    I: IntoIterator<Item = (String, Vec<E::ScalarField>)>
+> (
+    r1cs_file_path : &str,
+    key_file_path : &str,
+    wasm_file_path : &str,
+    sym_file_path : &str,
+    proof_file_path : &str,
+    signals : &[String],
+    inputs : I,
+    seed : u64,
+) -> Result<String, Error> {
+    let proving_key:ProvingKey<E> = read_compressed_proving_key_from_file::<E>(
+        key_file_path
+    )?;
+    let commit_witness_count = proving_key.vk.commit_witness_count;
+    if signals.len() != commit_witness_count {
+        return Err(Error::Circuit(format!(
+            "proving key commits {} witnesses, {} signals given",
+            commit_witness_count,
+            signals.len()
+        )));
+    }
+    let public_inputs_count = proving_key.vk.gamma_abc_g1.len() - 1 - commit_witness_count;
+    let sym = SymbolTable::read_from_file(sym_file_path)?;
+    sym.check_committed_signals(signals, public_inputs_count)?;
+
+    let circuit = circom_circuit::<E, I>(r1cs_file_path, wasm_file_path, Some(&sym), inputs)?;
+    let (proof, opening_key) = prove_circuit::<E, _>(
+        CircomCommittedCircuit { circuit, commit_witness_count },
+        &proving_key,
+        None,
+        seed
+    )?;
+    write(proof_file_path, proof_to_bytes(&proof))?;
+
+    Ok(SignalOpening { signals : signals.to_vec(), opening_key }.to_json())
+}
+
+// r1cs with the wires computed by the wasm witness calculator
+// sym : names the missing input signals
+fn circom_circuit<
+    E: Pairing,
+    I: IntoIterator<Item = (String, Vec<E::ScalarField>)>
+> (
+    r1cs_file_path : &str,
+    wasm_file_path : &str,
+    sym : Option<&SymbolTable>,
+    inputs : I,
+) -> Result<CircomCircuit<E>, Error> {
+    set_circom_wires::<E, I>(
+        CircomCircuit::<E>::from_r1cs_file(r1cs_file_path)?,
+        WitnessCalculator::<E>::from_wasm_file(wasm_file_path)?,
+        sym,
+        inputs
+    )
+}
+
+fn set_circom_wires<
+    E: Pairing,
+    I: IntoIterator<Item = (String, Vec<E::ScalarField>)>
+> (
+    mut circuit : CircomCircuit<E>,
+    mut wits_calc : WitnessCalculator<E>,
+    sym : Option<&SymbolTable>,
+    inputs : I,
+) -> Result<CircomCircuit<E>, Error> {
+    let inputs = inputs.into_iter().collect::<Vec<_>>();
+    check_inputs::<E>(&wits_calc, sym, &inputs)?;
+    let all_wires = wits_calc.calculate_witnesses(inputs, true)?;
 
     circuit.set_wires(all_wires);
+    Ok(circuit)
+}
 
-    let public_inputs = circuit.get_public_inputs().unwrap();
+// proves any circuit::CommittedCircuit, the committed witnesses are opened by the returned key
+// v : blinding of proof.d, random if None
+// Error::Commitment if the returned key does not open proof.d
+pub fn prove_circuit<E:Pairing, C:CommittedCircuit<E::ScalarField>>(
+    circuit : C,
+    proving_key : &ProvingKey<E>,
+    v : Option<E::ScalarField>,
+    seed : u64
+) -> Result<(Proof<E>, OpeningKey<E>), Error> {
+    if proving_key.vk.commit_witness_count != circuit.commit_witness_count() {
+        return Err(Error::Circuit(format!(
+            "proving key commits {} witnesses, circuit {}",
+            proving_key.vk.commit_witness_count,
+            circuit.commit_witness_count()
+        )));
+    }
     let committed_witnesses = circuit
-        .wires
-        .clone()
-        .unwrap()
-        .into_iter()
-        .skip(1 + public_inputs.len())
-        .take(commit_witness_count)
-        .collect::<Vec<_>>();
+        .committed_witnesses()
+        .ok_or_else(|| Error::Circuit("circuit without assignment".to_string()))?;
 
     let mut rng = StdRng::seed_from_u64(seed);
-    let v: <E as Pairing>::ScalarField = E::ScalarField::rand(&mut rng);
-    
+    let v: <E as Pairing>::ScalarField = v.unwrap_or_else(|| E::ScalarField::rand(&mut rng));
+
     let prove_start_time= SystemTime::now();
-    let proof = create_random_proof(circuit, v, &proving_key, &mut rng).unwrap();
+    let proof = create_random_proof(circuit, v, proving_key, &mut rng)?;
     let prove_end_time = SystemTime::now();
     let prove_duration = prove_end_time.duration_since(prove_start_time)
         .expect("SystemTime::duration_since failed");
-    println!("prove time: {:?}", prove_duration);
-    
-    // to debug
-    // println!("committed wit : {:?}", committed_witnesses);
-    // println!("proof.d : {}", proof.d);
-    // println!("calculated pedersen commitment: {}", proving_key.vk.gamma_abc_g1[1] * committed_witnesses[0] + &(proving_key.vk.eta_gamma_inv_g1.mul_bigint(v.into_bigint())));
-    // println!("calculated pedersen commitment: {}", proving_key.vk.gamma_abc_g1[1] * committed_witnesses[0] + (proving_key.vk.eta_gamma_inv_g1.mul_bigint(v.into_bigint())));
-    println!("v : {:?}", v.to_string());
+    log::debug!("prove time: {:?}", prove_duration);
 
-    let mut compressed_bytes:Vec<u8> = Vec::new();
-    proof.serialize_compressed(&mut compressed_bytes).unwrap();
-    write(
-        abs_path(proof_file_path),
-        compressed_bytes
-    ).unwrap();
+    // committed_witnesses() of a hand-written circuit may not match what it allocates
+    if calculate_pedersen_commitment::<E>(&proving_key.vk, &committed_witnesses, v)? != proof.d {
+        return Err(Error::Commitment(
+            "committed witnesses of the circuit do not open proof.d".to_string()
+        ));
+    }
 
-    // to debug
-    // println!("test to_bytes_le : {:?}", v.into_bigint().to_bytes_le());
-    // println!("test to_bytes_be : {:?}", v.into_bigint().to_bytes_be());
-    // println!("test from bytes_be : {:?}", E::ScalarField::from_be_bytes_mod_order(&v.into_bigint().to_bytes_be()).to_string());
-    // println!("test to hex string : {}", v.into_bigint().to_bytes_be().encode_hex::<String>());åå
-    // println!("test commited : {}", committed_witnesses[0].into_bigint());
+    Ok((proof, OpeningKey::<E>::new(committed_witnesses, v)))
+}
 
-    serde_json::to_string(&serde_json::json!({
-        "m" : committed_witnesses[0].into_bigint().to_bytes_be().encode_hex::<String>(),
-        "v" : v.into_bigint().to_bytes_be().encode_hex::<String>()
-    })).unwrap()
+// prove_circuit with the key and proof files of prove
+// return pedersen commitment opening keys (m, v)
+pub fn prove_circuit_to_file<E:Pairing, C:CommittedCircuit<E::ScalarField>>(
+    circuit : C,
+    key_file_path : &str,
+    proof_file_path : &str,
+    seed : u64
+) -> Result<String, Error> {
+    let proving_key = read_compressed_proving_key_from_file::<E>(key_file_path)?;
+    let (proof, opening_key) = prove_circuit::<E, C>(circuit, &proving_key, None, seed)?;
+    write(proof_file_path, proof_to_bytes(&proof))?;
+    Ok(opening_key.to_json())
 }
 
-pub fn make_range_inputs<E:Pairing> (
-    input_string: String
-) -> E::ScalarField {
-    let mut input_str = input_string.as_str();
-    input_str = input_str.trim_start_matches("0x");
+pub fn proof_to_bytes<E:Pairing>(
+    proof : &Proof<E>
+) -> Vec<u8> {
+    let mut compressed_bytes:Vec<u8> = Vec::new();
+    proof.serialize_compressed(&mut compressed_bytes).unwrap();
+    compressed_bytes
+}
 
-    E::ScalarField::from(u64::from_str_radix(input_str, 16).unwrap())
+// input_string : decimal ("1234", "-1") or hex ("0x04d2")
+pub fn make_range_inputs<E:Pairing> (
+    input_string: String,
+    mode : ReduceMode
+) -> Result<E::ScalarField, FieldParseError> {
+    parse_field_element::<E::ScalarField>(input_string.as_str(), mode)
 }
 
+// hex_string : hex with or without "0x", unprefixed digits are hex too ("10" is 16)
+// opening keys are canonical, so anything >= field order is rejected
+// (it used to be reduced mod the field order)
 pub fn hex_string_to_scalar_field<E:Pairing> (
     hex_string: String
-) ->  E::ScalarField {
-    let mut hex_string = hex_string.as_str();
-    hex_string = hex_string.trim_start_matches("0x");
-    let bytes = hex::decode(format!("{:0>64}", hex_string)).unwrap();
-
-    E::ScalarField::from_be_bytes_mod_order(
-        &bytes
-    )
+) -> Result<E::ScalarField, Error> {
+    Ok(parse_hex_field_element::<E::ScalarField>(hex_string.as_str(), ReduceMode::Strict)?)
 }
 
 pub fn proof_to_string_from_file<E:Pairing> (
     proof_file_path: &str
-) -> String {
-    let proof_bin = read(abs_path(proof_file_path)).unwrap();
-    let proof = Proof::<E>::deserialize_compressed(&*proof_bin).unwrap();
-    proof_to_string(proof)
+) -> Result<String, Error> {
+    Ok(proof_to_string(read_proof_from_file::<E>(proof_file_path)?))
 }
 
 pub fn proof_to_string<E:Pairing> (
@@ -145,115 +397,114 @@ pub fn proof_to_string<E:Pairing> (
     }).to_string()
 }
 
-// to calculate g*m + h*v, originally m and g are vector
-// m : message
+// to calculate g_0*m_0 + ... + g_n-1*m_n-1 + h*v
+// m : committed witnesses (message)
 // v : random
-// g : proving_key.vk.gamma_abc_g1
-// h : proving_key.vk.eta_gamma_inv_g1
+// g : `vk.gamma_abc_g1[1 + public_inputs_count ..]`, `gamma_abc_g1[0]` is for the constant 1
+// h : vk.eta_gamma_inv_g1
 pub fn calculate_pedersen_commitment<E:Pairing>(
-    proving_key : ProvingKey<E>,
-    m : E::ScalarField,
+    vk : &VerifyingKey<E>,
+    m : &[E::ScalarField],
     v : E::ScalarField
-) -> E::G1Affine {
-    println!("==== calculate_pedersen_commitment ====");
-    println!("m : {}", m);
-    println!("v : {}", v);
+) -> Result<E::G1Affine, Error> {
+    if m.len() != vk.commit_witness_count {
+        return Err(Error::Commitment(format!(
+            "expected {} committed witnesses, got {}",
+            vk.commit_witness_count,
+            m.len()
+        )));
+    }
 
-    (proving_key.vk.gamma_abc_g1[1] * m + proving_key.vk.eta_gamma_inv_g1 * v).into()
+    let public_inputs_count = vk.gamma_abc_g1.len() - 1 - vk.commit_witness_count;
+    let g = &vk.gamma_abc_g1[1 + public_inputs_count..];
+
+    Ok(g.iter()
+        .zip(m)
+        .fold(vk.eta_gamma_inv_g1 * v, |commitment, (g, m)| commitment + *g * m)
+        .into())
+}
+
+// recompute proof.d from the opening key and compare
+pub fn check_pedersen_commitment<E:Pairing>(
+    vk_file_path : &str,
+    proof_file_path : &str,
+    opening_key_file_path : &str
+) -> Result<bool, Error> {
+    let vk = read_verifying_key_from_file::<E>(vk_file_path)?;
+    let proof = read_proof_from_file::<E>(proof_file_path)?;
+    let opening_key = OpeningKey::<E>::read_from_file(opening_key_file_path)?;
+
+    Ok(opening_key.commit(&vk)? == Commitment::from_proof(&proof))
 }
 
 pub fn aggregate_proof_commitment<E:Pairing>(
     proof_file_paths : Vec<String>,
     save_file_path : &str
-) {
-    println!("==== aggregate_proof_commitment ====");
+) -> Result<(), Error> {
+    log::debug!("==== aggregate_proof_commitment ====");
 
-    assert!(proof_file_paths.len() != 0);
-    let mut result = Proof::<E>::deserialize_compressed(
-        &*read(abs_path(&proof_file_paths[0])).unwrap()
-    ).unwrap().d;
+    if proof_file_paths.is_empty() {
+        return Err(Error::Commitment("no proofs to aggregate".to_string()));
+    }
+    let mut result = read_proof_commitment::<E>(&proof_file_paths[0])?;
 
     for proof_file_path in proof_file_paths.iter().skip(1) {
-        let proof = Proof::<E>::deserialize_compressed(
-            &*read(abs_path(proof_file_path)).unwrap()
-        ).unwrap().d;
-        result = add_pedersen_commitment::<E>(result, proof);
+        result = result.add(&read_proof_commitment::<E>(proof_file_path)?);
     }
-    
-    let mut compressed_bytes:Vec<u8> = Vec::new();
-    result.serialize_compressed(&mut compressed_bytes).unwrap();
-    write(
-        abs_path(save_file_path),
-        compressed_bytes
-    ).unwrap();
+
+    result.write_to_file(save_file_path)
+}
+
+pub fn read_proof_from_file<E:Pairing>(
+    proof_file_path : &str
+) -> Result<Proof<E>, Error> {
+    Ok(Proof::<E>::deserialize_compressed(&*read(proof_file_path)?)?)
+}
+
+pub fn read_proof_commitment<E:Pairing>(
+    proof_file_path : &str
+) -> Result<Commitment<E>, Error> {
+    Ok(Commitment::from_proof(&read_proof_from_file::<E>(proof_file_path)?))
 }
 
 pub fn get_aggregated_commitment<E:Pairing>(
     aggregated_commitment_file_path : &str
-) -> String {
-    let aggregated_commitment_bin = read(abs_path(aggregated_commitment_file_path)).unwrap();
-    let aggregated_commitment = E::G1Affine::deserialize_compressed(&*aggregated_commitment_bin).unwrap();
-    format!("{:#?}", aggregated_commitment)
+) -> Result<String, Error> {
+    let aggregated_commitment = Commitment::<E>::read_from_file(aggregated_commitment_file_path)?;
+    Ok(format!("{:?}", aggregated_commitment))
 }
 
 // save as json
 pub fn aggregated_pedersen_commitment_opening_keys<E:Pairing>(
     opening_key_paths : Vec<String>,
     save_file_path : &str
-) {
-    assert!(opening_key_paths.len() != 0);
-    
-    let opening_key_json : serde_json::Value = serde_json::from_str(
-        std::str::from_utf8(
-            &(read(abs_path(&opening_key_paths[0])).unwrap())
-        ).unwrap()
-    ).unwrap();
-    println!("{}", std::str::from_utf8(
-        &(read(abs_path(&opening_key_paths[0])).unwrap())
-    ).unwrap());
-
-    let mut aggregated_v = hex_string_to_scalar_field::<E>(
-        opening_key_json["v"].as_str().unwrap().to_string()
-    );
-    let mut aggregated_m = hex_string_to_scalar_field::<E>(
-        opening_key_json["m"].as_str().unwrap().to_string()
-    );
-
-    for opening_key_path in opening_key_paths.iter().skip(1) {
-        let opening_key_json : serde_json::Value = serde_json::from_str(
-            std::str::from_utf8(
-                &(read(abs_path(&opening_key_path)).unwrap())
-            ).unwrap()
-        ).unwrap();
-        let v = hex_string_to_scalar_field::<E>(
-            opening_key_json["v"].as_str().unwrap().to_string()
-        );
-        let m = hex_string_to_scalar_field::<E>(
-            opening_key_json["m"].as_str().unwrap().to_string()
-        );
-
-        aggregated_v = aggregated_v + v;
-        aggregated_m = aggregated_m + m;
+) -> Result<(), Error> {
+    if opening_key_paths.is_empty() {
+        return Err(Error::Commitment("no opening keys to aggregate".to_string()));
     }
 
-    println!("aggregated_v : {}", aggregated_v);
-    println!("aggregated_m : {}", aggregated_m);
+    let mut aggregated_opening_key = OpeningKey::<E>::read_from_file(&opening_key_paths[0])?;
 
-    let aggregated_opening_key =  serde_json::to_string(&serde_json::json!({
-        "m" : aggregated_m.into_bigint().to_bytes_be().encode_hex::<String>(),
-        "v" : aggregated_v.into_bigint().to_bytes_be().encode_hex::<String>()
-    })).unwrap();
+    for opening_key_path in opening_key_paths.iter().skip(1) {
+        // vector commitments are summed element-wise
+        aggregated_opening_key = aggregated_opening_key
+            .add(&OpeningKey::<E>::read_from_file(opening_key_path)?)?;
+    }
 
     write(
         save_file_path, 
-        aggregated_opening_key
-    ).unwrap();
+        aggregated_opening_key.to_json()
+    )?;
+    Ok(())
 }
 
-// 너무 코드가 드러움....... 고치기는 귀찮아 ..................
-// 변수 리팩토링 해야함
 // proof file path : updated user proof 
 // aggregated_commitment_file_path : aggregated commitment
+// update_values : one value per committed witness of the member opening key,
+// a single value is the circuit input "value", several are "values" (range_vector circuits)
+// the user proof, its opening key and both aggregated files are replaced in one transaction,
+// see transaction.rs. an interrupted update is recovered on the next call
+#[allow(clippy::too_many_arguments)]
 pub fn update_aggregated_commitment<E:Pairing>(
     r1cs_file_path : &str,
     key_file_path : &str,
@@ -261,121 +512,482 @@ pub fn update_aggregated_commitment<E:Pairing>(
     proof_file_path : &str,
     aggregated_commitment_file_path : &str,
     aggregated_opening_key_file_path : &str,
-    update_value : String,
+    update_values : Vec<String>,
+    mode : ReduceMode,
     seed : u64
-) {
-    let opening_key_path = format!("{}{}",proof_file_path.trim_end_matches(".bin"), "_opening_key.json");
-    let opening_key_json : serde_json::Value = serde_json::from_str(
-        std::str::from_utf8(
-            &(read(abs_path(&opening_key_path)).unwrap())
-        ).unwrap()
-    ).unwrap();
-
-    let aggregated_opening_key_json : serde_json::Value = serde_json::from_str(
-        std::str::from_utf8(
-            &(read(abs_path(&aggregated_opening_key_file_path)).unwrap())
-        ).unwrap()
-    ).unwrap();
-
-    let before_v = hex_string_to_scalar_field::<E>(
-        opening_key_json["v"].as_str().unwrap().to_string()
-    );
-    let before_m = hex_string_to_scalar_field::<E>(
-        opening_key_json["m"].as_str().unwrap().to_string()
-    );
-    let mut aggregated_v = hex_string_to_scalar_field::<E>(
-        aggregated_opening_key_json["v"].as_str().unwrap().to_string()
-    );
-    let mut aggregated_m = hex_string_to_scalar_field::<E>(
-        aggregated_opening_key_json["m"].as_str().unwrap().to_string()
-    );
+) -> Result<(), Error> {
+    // parse first, nothing is written if a value is invalid
+    let inputs_values = update_values
+        .into_iter()
+        .map(|value| make_range_inputs::<E>(value, mode))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    // sub before opening keys
-    aggregated_m = aggregated_m - before_m;
-    aggregated_v = aggregated_v - before_v;
+    let journal_path = aggregated_commitment_journal_path(aggregated_commitment_file_path);
+    transaction::recover(journal_path.as_str())?;
 
-    let aggregated_commitment_bin = read(abs_path(aggregated_commitment_file_path)).unwrap();
-    let mut aggregated_commitment = E::G1Affine::deserialize_compressed(&*aggregated_commitment_bin).unwrap();
+    let opening_key_path = format!("{}{}",proof_file_path.trim_end_matches(".bin"), "_opening_key.json");
+    let before_opening_key = OpeningKey::<E>::read_from_file(&opening_key_path)?;
+    let commit_witness_count = before_opening_key.m.len();
+    if inputs_values.len() != commit_witness_count {
+        return Err(Error::Commitment(format!(
+            "{} update values for an opening key of {} values", inputs_values.len(), commit_witness_count
+        )));
+    }
+    let mut aggregated_opening_key = OpeningKey::<E>::read_from_file(aggregated_opening_key_file_path)?;
+    let mut aggregated_commitment = Commitment::<E>::read_from_file(aggregated_commitment_file_path)?;
 
-    let mut proof_bin = read(abs_path(proof_file_path)).unwrap();
-    let mut proof: Proof<E> = Proof::<E>::deserialize_compressed(&*proof_bin).unwrap();
+    // sub before opening keys and aggregated commitment - before proof.d
+    aggregated_opening_key = aggregated_opening_key.sub(&before_opening_key)?;
+    aggregated_commitment = aggregated_commitment.sub(&read_proof_commitment::<E>(proof_file_path)?);
 
-    // aggregated commitment - update_proof_d
-    aggregated_commitment = (aggregated_commitment.into_group() - proof.d.into_group()).into();
-    
-    let inputs_value = make_range_inputs::<E>(update_value);
+    let input_name = if commit_witness_count == 1 { "value" } else { "values" };
     let mut inputs= HashMap ::new();
-    inputs.insert("value".to_string(), vec![inputs_value]);
-    let after_opening_key = prove::<E, _>(
+    inputs.insert(input_name.to_string(), inputs_values);
+    let (after_proof, after_opening_key) = create_proof::<E, _>(
         r1cs_file_path, 
         key_file_path, 
         wasm_file_path, 
-        proof_file_path, 
-        1, 
+        commit_witness_count, 
         inputs, 
         seed
-    );
-    proof_bin = read(abs_path(proof_file_path)).unwrap();
-    proof = Proof::<E>::deserialize_compressed(&*proof_bin).unwrap();
+    )?;
 
-    let after_opening_key_json : serde_json::Value = serde_json::from_str(&after_opening_key).unwrap();
-    let after_v = hex_string_to_scalar_field::<E>(
-        after_opening_key_json["v"].as_str().unwrap().to_string()
-    );
-    let after_m = hex_string_to_scalar_field::<E>(
-        after_opening_key_json["m"].as_str().unwrap().to_string()
-    );
+    aggregated_opening_key = aggregated_opening_key.add(&after_opening_key)?;
+    aggregated_commitment = aggregated_commitment.add(&Commitment::from_proof(&after_proof));
 
-    aggregated_v = aggregated_v + after_v;
-    aggregated_m = aggregated_m + after_m;
+    let mut txn = FileTransaction::new(journal_path.as_str());
+    txn.write(proof_file_path, proof_to_bytes(&after_proof));
+    txn.write(opening_key_path.as_str(), after_opening_key.to_json());
+    txn.write(aggregated_opening_key_file_path, aggregated_opening_key.to_json());
+    txn.write(aggregated_commitment_file_path, aggregated_commitment.to_bytes());
+    txn.commit()
+}
 
-    aggregated_commitment = (aggregated_commitment + proof.d).into();
+// subtracts a member from the aggregate
+// the member proof must verify and its opening key must open proof.d, otherwise nothing is removed
+pub fn remove_from_aggregate<E:Pairing>(
+    vk : &VerifyingKey<E>,
+    aggregated_commitment : &Commitment<E>,
+    aggregated_opening_key : &OpeningKey<E>,
+    proof : &Proof<E>,
+    opening_key : &OpeningKey<E>,
+    public_inputs : &[E::ScalarField]
+) -> Result<(Commitment<E>, OpeningKey<E>), Error> {
+    if verify_proof(&prepare_verifying_key(vk), proof, public_inputs).is_err() {
+        return Err(Error::Verification("member proof is invalid".to_string()));
+    }
+    if opening_key.commit(vk)? != Commitment::from_proof(proof) {
+        return Err(Error::Verification("member opening key does not open proof.d".to_string()));
+    }
 
-    let aggregated_opening_key =  serde_json::to_string(&serde_json::json!({
-        "m" : aggregated_m.into_bigint().to_bytes_be().encode_hex::<String>(),
-        "v" : aggregated_v.into_bigint().to_bytes_be().encode_hex::<String>()
-    })).unwrap();
+    Ok((
+        aggregated_commitment.sub(&Commitment::from_proof(proof)),
+        aggregated_opening_key.sub(opening_key)?
+    ))
+}
 
-    write(
-        aggregated_opening_key_file_path, 
-        aggregated_opening_key
-    ).unwrap();
+// file version of remove_from_aggregate, both aggregated files are replaced in one transaction
+// opening_key_file_path : defaults to `<proof>_opening_key.json`
+// public_inputs : public inputs of the member proof, in circuit order
+pub fn remove_from_aggregate_file<E:Pairing>(
+    vk_file_path : &str,
+    proof_file_path : &str,
+    opening_key_file_path : Option<&str>,
+    aggregated_commitment_file_path : &str,
+    aggregated_opening_key_file_path : &str,
+    public_inputs : &[E::ScalarField]
+) -> Result<(), Error> {
+    let journal_path = aggregated_commitment_journal_path(aggregated_commitment_file_path);
+    transaction::recover(journal_path.as_str())?;
+
+    let opening_key_path = match opening_key_file_path {
+        Some(path) => path.to_string(),
+        None => format!("{}{}", proof_file_path.trim_end_matches(".bin"), "_opening_key.json"),
+    };
+    let vk = read_verifying_key_from_file::<E>(vk_file_path)?;
+    let (aggregated_commitment, aggregated_opening_key) = remove_from_aggregate::<E>(
+        &vk,
+        &Commitment::<E>::read_from_file(aggregated_commitment_file_path)?,
+        &OpeningKey::<E>::read_from_file(aggregated_opening_key_file_path)?,
+        &read_proof_from_file::<E>(proof_file_path)?,
+        &OpeningKey::<E>::read_from_file(opening_key_path.as_str())?,
+        public_inputs
+    )?;
+
+    let mut txn = FileTransaction::new(journal_path.as_str());
+    txn.write(aggregated_opening_key_file_path, aggregated_opening_key.to_json());
+    txn.write(aggregated_commitment_file_path, aggregated_commitment.to_bytes());
+    txn.commit()
+}
 
-    let mut compressed_bytes:Vec<u8> = Vec::new();
-    aggregated_commitment.serialize_compressed(&mut compressed_bytes).unwrap();
-    write(
-        aggregated_commitment_file_path,
-        compressed_bytes
-    ).unwrap();
+// reveals the total m of an aggregated opening key without its blinding v
+// proves knowledge of v with  aggregated commitment - g*m = h*v
+pub fn prove_aggregated_total<E:Pairing>(
+    vk : &VerifyingKey<E>,
+    aggregated_opening_key : &OpeningKey<E>
+) -> Result<SchnorrProof<E::G1Affine>, Error> {
+    let blinding : E::G1Affine = (vk.eta_gamma_inv_g1 * aggregated_opening_key.v).into();
+    SchnorrProof::prove(
+        AGGREGATED_TOTAL_LABEL,
+        &[],
+        &[vk.eta_gamma_inv_g1],
+        &blinding,
+        &[aggregated_opening_key.v]
+    )
+}
+
+// bases of proof.d for every committed witness
+pub fn pedersen_bases<E:Pairing>(
+    vk : &VerifyingKey<E>
+) -> PedersenBases<E::G1Affine> {
+    let public_inputs_count = vk.gamma_abc_g1.len() - 1 - vk.commit_witness_count;
+    PedersenBases::new(
+        vk.gamma_abc_g1[1 + public_inputs_count..].to_vec(),
+        vk.eta_gamma_inv_g1
+    )
+}
+
+// proves that proof.d (opened by opening_key) and  external_bases.g*m + external_bases.h*external_blinding
+// commit to the same m, without revealing m or the blindings
+pub fn prove_commitment_equality<E:Pairing>(
+    vk : &VerifyingKey<E>,
+    opening_key : &OpeningKey<E>,
+    external_bases : &PedersenBases<E::G1Affine>,
+    external_blinding : E::ScalarField
+) -> Result<EqualityProof<E::G1Affine>, Error> {
+    EqualityProof::prove(
+        COMMITMENT_EQUALITY_LABEL,
+        [&pedersen_bases::<E>(vk), external_bases],
+        &opening_key.m,
+        [opening_key.v, external_blinding]
+    )
+}
+
+// proves knowledge of {m, v} with  commitment = g*m + h*v  without revealing them
+// works for proof.d and for an aggregated commitment with its aggregated opening key
+pub fn prove_opening<E:Pairing>(
+    vk : &VerifyingKey<E>,
+    opening_key : &OpeningKey<E>,
+    context : &[u8]
+) -> Result<SchnorrProof<E::G1Affine>, Error> {
+    let commitment = opening_key.commit(vk)?;
+    let bases = pedersen_bases::<E>(vk).opening_bases(opening_key.m.len())?;
+    let mut witnesses = opening_key.m.clone();
+    witnesses.push(opening_key.v);
+
+    SchnorrProof::prove(OPENING_LABEL, context, &bases, &commitment.point, &witnesses)
 }
 
-pub fn add_pedersen_commitment_from_proof_file<E:Pairing>(
-    proof_one_file_path:&str,
-    proof_two_file_path:&str,
-) -> E::G1Affine {
-    let proof_one: Proof<E> = Proof::<E>::deserialize_compressed(&*read(proof_one_file_path).unwrap()).unwrap();
-    let proof_two: Proof<E> = Proof::<E>::deserialize_compressed(&*read(proof_two_file_path).unwrap()).unwrap();
-    add_pedersen_commitment_from_proof(proof_one, proof_two)
+// fresh proof of the same statement, a, b and c are unlinkable to the original
+//   a' = a/r1,  b' = r1*b + r1*r2*delta_g2,  c' = c + r2*a
+// proof.d is kept, it still opens with the same opening key and stays in aggregated commitments
+pub fn rerandomize_proof<E:Pairing>(
+    vk : &VerifyingKey<E>,
+    proof : &Proof<E>
+) -> Proof<E> {
+    let mut rng = StdRng::from_entropy();
+    let (r1, r2) = nonzero_pair::<E::ScalarField>(&mut rng);
+
+    Proof {
+        a : (proof.a * r1.inverse().unwrap()).into_affine(),
+        b : (proof.b * r1 + vk.delta_g2 * (r1 * r2)).into_affine(),
+        c : (proof.c + proof.a * r2).into_affine(),
+        d : proof.d,
+    }
+}
+
+// same as rerandomize_proof but proof.d is re-blinded as well, so the whole proof is unlinkable
+//   d' = d + h*delta, the opening key becomes {m, v + delta}
+// needs eta_delta_inv_g1 of the proving key
+pub fn rerandomize_proof_and_commitment<E:Pairing>(
+    pk : &ProvingKey<E>,
+    proof : &Proof<E>
+) -> (Proof<E>, E::ScalarField) {
+    let mut rng = StdRng::from_entropy();
+    let delta = E::ScalarField::rand(&mut rng);
+    let proof = rerandomize_proof_1(
+        proof,
+        E::ScalarField::zero(),
+        delta,
+        &pk.vk,
+        &pk.common.eta_delta_inv_g1,
+        &mut rng
+    );
+    (proof, delta)
+}
+
+fn nonzero_pair<F:Field>(rng : &mut StdRng) -> (F, F) {
+    loop {
+        let (r1, r2) = (F::rand(rng), F::rand(rng));
+        if !r1.is_zero() && !r2.is_zero() {
+            return (r1, r2);
+        }
+    }
 }
 
-pub fn add_pedersen_commitment_from_proof<E : Pairing>(
-    proof_one : Proof<E>,
-    proof_two : Proof<E>
-) -> E::G1Affine {
-    add_pedersen_commitment::<E>(proof_one.d.clone(), proof_two.d.clone())
+// range proof about the aggregated total without revealing it, using the single value range circuit
+// (`range_proof`, `range_bits_<n>`) and the key the members proved with
+//   limit = None : proves 0 <= total < 2^n,         proof.d == aggregated commitment
+//   limit = l    : proves 0 <= l - total < 2^n,     proof.d == g*l - aggregated commitment
+// since proof.d is fixed by the aggregated commitment, the committed value is the hidden total.
+// bit_width : n of the circuit, l must be < 2^n so l - total does not wrap around the field
+// for totals of range proven members
+#[allow(clippy::too_many_arguments)]
+pub fn prove_aggregate_range<E:Pairing>(
+    r1cs_file_path : &str,
+    key_file_path : &str,
+    wasm_file_path : &str,
+    aggregated_opening_key : &OpeningKey<E>,
+    bit_width : usize,
+    limit : Option<E::ScalarField>,
+    seed : u64
+) -> Result<Proof<E>, Error> {
+    check_aggregate_limit::<E::ScalarField>(bit_width, limit)?;
+    let (total, blinding) = match aggregated_opening_key.m.as_slice() {
+        [total] => (*total, aggregated_opening_key.v),
+        m => return Err(Error::Commitment(format!("expected 1 committed witness, got {}", m.len()))),
+    };
+
+    let (value, v) = match limit {
+        Some(limit) => {
+            let total_int : BigUint = total.into();
+            let limit_int : BigUint = limit.into();
+            if total_int > limit_int {
+                return Err(Error::InvalidRange("aggregated total exceeds the limit".to_string()));
+            }
+            (limit - total, -blinding)
+        },
+        None => (total, blinding),
+    };
+
+    let mut inputs = HashMap::new();
+    inputs.insert("value".to_string(), vec![value]);
+    let (proof, _) = create_proof_with_blinding::<E, _>(
+        r1cs_file_path,
+        key_file_path,
+        wasm_file_path,
+        1,
+        inputs,
+        Some(v),
+        seed
+    )?;
+    Ok(proof)
 }
 
-pub fn add_pedersen_commitment<E : Pairing> (
-    commitment_one : E::G1Affine,
-    commitment_two : E::G1Affine
-) -> E::G1Affine {
-    (commitment_one + commitment_two).into()
+// limit < 2^bit_width, shared by prove_aggregate_range and verifier::verify_aggregate_range
+pub fn check_aggregate_limit<F:PrimeField>(
+    bit_width : usize,
+    limit : Option<F>
+) -> Result<(), Error> {
+    check_bit_width::<F>(bit_width)?;
+    if let Some(limit) = limit {
+        let limit_int : BigUint = limit.into();
+        if limit_int.bits() as usize > bit_width {
+            return Err(Error::InvalidRange(format!("limit {} is not less than 2^{}", limit_int, bit_width)));
+        }
+    }
+    Ok(())
 }
 
-pub fn add_pedersen_commitment_and_proof<E:Pairing>(
-    proof : Proof<E>,
-    commitment : E::G1Affine
-) -> E::G1Affine {
-    add_pedersen_commitment::<E>(proof.d, commitment)
+// aggregated_commitment.bin -> aggregated_commitment.bin.journal
+pub fn aggregated_commitment_journal_path(
+    aggregated_commitment_file_path : &str
+) -> String {
+    format!("{}.journal", aggregated_commitment_file_path)
+}
+
+// native circuits only, the circom paths need the wasm witness calculator
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Bn254, Fr};
+    use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+    use crate::circuit::{test_util::{prove_values, range_pk}, RangeCircuit};
+
+    #[test]
+    fn removes_a_verified_member() {
+        let pk = range_pk(1);
+        let (proof_a, key_a) = prove_values(&pk, &[10], 1);
+        let (proof_b, key_b) = prove_values(&pk, &[20], 2);
+        let aggregated_commitment = Commitment::from_proof(&proof_a).add(&Commitment::from_proof(&proof_b));
+        let aggregated_opening_key = key_a.add(&key_b).unwrap();
+
+        let (commitment, opening_key) = remove_from_aggregate::<Bn254>(
+            &pk.vk, &aggregated_commitment, &aggregated_opening_key, &proof_b, &key_b, &[]
+        ).unwrap();
+        assert_eq!(commitment, Commitment::from_proof(&proof_a));
+        assert_eq!(opening_key, key_a);
+    }
+
+    #[test]
+    fn rejects_invalid_members() {
+        let pk = range_pk(1);
+        let (proof_a, key_a) = prove_values(&pk, &[10], 1);
+        let (proof_b, key_b) = prove_values(&pk, &[20], 2);
+        let aggregated_commitment = Commitment::from_proof(&proof_a).add(&Commitment::from_proof(&proof_b));
+        let aggregated_opening_key = key_a.add(&key_b).unwrap();
+
+        // opening key of another member
+        assert!(matches!(
+            remove_from_aggregate::<Bn254>(&pk.vk, &aggregated_commitment, &aggregated_opening_key, &proof_b, &key_a, &[]),
+            Err(Error::Verification(_))
+        ));
+        // public inputs the proof was not made for
+        assert!(matches!(
+            remove_from_aggregate::<Bn254>(&pk.vk, &aggregated_commitment, &aggregated_opening_key, &proof_b, &key_b, &[Fr::from(1u64)]),
+            Err(Error::Verification(_))
+        ));
+    }
+
+    #[test]
+    fn rerandomized_proof_verifies_and_keeps_d() {
+        let pk = range_pk(1);
+        let (proof, opening_key) = prove_values(&pk, &[42], 1);
+        let pvk = prepare_verifying_key(&pk.vk);
+
+        let rerandomized = rerandomize_proof::<Bn254>(&pk.vk, &proof);
+        assert!(verify_proof(&pvk, &rerandomized, &[]).is_ok());
+        assert_ne!((rerandomized.a, rerandomized.b, rerandomized.c), (proof.a, proof.b, proof.c));
+        assert_eq!(opening_key.commit(&pk.vk).unwrap(), Commitment::from_proof(&rerandomized));
+    }
+
+    #[test]
+    fn rerandomized_commitment_opens_with_shifted_blinding() {
+        let pk = range_pk(2);
+        let (proof, opening_key) = prove_values(&pk, &[42, 7], 1);
+
+        let (rerandomized, delta) = rerandomize_proof_and_commitment::<Bn254>(&pk, &proof);
+        assert!(verify_proof(&prepare_verifying_key(&pk.vk), &rerandomized, &[]).is_ok());
+        assert_ne!(rerandomized.d, proof.d);
+        let shifted = OpeningKey::<Bn254>::new(opening_key.m.clone(), opening_key.v + delta);
+        assert_eq!(shifted.commit(&pk.vk).unwrap(), Commitment::from_proof(&rerandomized));
+        assert_ne!(opening_key.commit(&pk.vk).unwrap(), Commitment::from_proof(&rerandomized));
+    }
+
+    // setup, prove and verify without any file but the shipped circuit
+    #[test]
+    fn buffer_setup_prove_and_verify() {
+        use crate::{keys::compressed_proving_key_bytes, setup::setup_from_circom_r1cs_bytes, verifier::verify_circuit_proof};
+
+        let r1cs = std::fs::read("circom/bn128/range_proof.r1cs").unwrap();
+        let wasm = std::fs::read("circom/bn128/range_proof.wasm").unwrap();
+        let proving_key = setup_from_circom_r1cs_bytes::<Bn254>(&r1cs, 1, 0).unwrap();
+        let (pk_bytes, vk_bytes) = compressed_proving_key_bytes::<Bn254>(&proving_key).unwrap();
+
+        let inputs = [("value".to_string(), vec![Fr::from(42u64)])];
+        let (proof_bytes, opening_key) = prove_from_key_bytes::<Bn254, _>(&r1cs, &pk_bytes, &wasm, 1, inputs, 1).unwrap();
+
+        let vk = VerifyingKey::<Bn254>::deserialize_compressed(&*vk_bytes).unwrap();
+        let proof = Proof::<Bn254>::deserialize_compressed(&*proof_bytes).unwrap();
+        assert!(verify_circuit_proof::<Bn254>(&vk, &proof, &[]));
+        let opening_key = OpeningKey::<Bn254>::from_json(&opening_key).unwrap();
+        assert_eq!(opening_key.m, vec![Fr::from(42u64)]);
+        assert_eq!(opening_key.commit(&vk).unwrap(), Commitment::from_proof(&proof));
+    }
+
+    #[test]
+    fn prove_signals_with_the_shipped_range_proof() {
+        let dir = std::env::temp_dir().join(format!("prover_{}_signals", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = |name : &str| dir.join(name).to_string_lossy().to_string();
+        let (r1cs, wasm, sym) = ("circom/bn128/range_proof.r1cs", "circom/bn128/range_proof.wasm", "circom/bn128/range_proof.sym");
+        let (pk, vk, proof) = (path("pk.bin"), path("vk.bin"), path("proof.bin"));
+        let proving_key = crate::setup::setup_from_circom_r1cs_bytes::<Bn254>(&std::fs::read(r1cs).unwrap(), 1, 0).unwrap();
+        crate::keys::write_to_file_compresed_proving_key::<Bn254>(proving_key, &pk, &vk).unwrap();
+        let inputs = || [("value".to_string(), vec![Fr::from(42u64)])];
+
+        let opening = prove_signals::<Bn254, _>(r1cs, &pk, wasm, sym, &proof, &["value".to_string()], inputs(), 1).unwrap();
+        let json : serde_json::Value = serde_json::from_str(&opening).unwrap();
+        assert_eq!(json["signals"]["main.value"], crate::field::field_element_to_hex(&Fr::from(42u64)));
+        let opening_key = OpeningKey::<Bn254>::from_json(&opening).unwrap();
+        let verifying_key = read_verifying_key_from_file::<Bn254>(&vk).unwrap();
+        assert_eq!(opening_key.commit(&verifying_key).unwrap(), read_proof_commitment::<Bn254>(&proof).unwrap());
+        assert!(crate::verifier::verify::<Bn254>(&vk, &proof, vec![]).unwrap());
+
+        // the first committed wire is main.value, not a bit
+        assert!(matches!(
+            prove_signals::<Bn254, _>(r1cs, &pk, wasm, sym, &proof, &["main.bitsN.bits[0]".to_string()], inputs(), 1),
+            Err(Error::Circuit(_))
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // a failing proof (value out of range) must leave the proof, its opening key and the aggregate as they were
+    #[test]
+    fn failed_update_leaves_the_files_unchanged() {
+        let dir = std::env::temp_dir().join(format!("prover_{}_failed_update", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = |name : &str| dir.join(name).to_string_lossy().to_string();
+        let (r1cs, wasm) = ("circom/bn128/range_bits_8.r1cs", "circom/bn128/range_bits_8.wasm");
+        let (pk, vk) = (path("pk.bin"), path("vk.bin"));
+        let proving_key = crate::setup::setup_from_circom_r1cs_bytes::<Bn254>(&std::fs::read(r1cs).unwrap(), 1, 0).unwrap();
+        crate::keys::write_to_file_compresed_proving_key::<Bn254>(proving_key, &pk, &vk).unwrap();
+
+        let mut aggregated_commitment : Option<Commitment<Bn254>> = None;
+        let mut aggregated_opening_key : Option<OpeningKey<Bn254>> = None;
+        for (name, value) in [("a", 10u64), ("b", 20)] {
+            let inputs = [("value".to_string(), vec![Fr::from(value)])];
+            let opening_key = prove::<Bn254, _>(r1cs, &pk, wasm, &path(&format!("{}.bin", name)), 1, inputs, value).unwrap();
+            std::fs::write(path(&format!("{}_opening_key.json", name)), &opening_key).unwrap();
+            let opening_key = OpeningKey::<Bn254>::from_json(&opening_key).unwrap();
+            let commitment = read_proof_commitment::<Bn254>(&path(&format!("{}.bin", name))).unwrap();
+            aggregated_commitment = Some(match aggregated_commitment {
+                Some(aggregated) => aggregated.add(&commitment),
+                None => commitment,
+            });
+            aggregated_opening_key = Some(match aggregated_opening_key {
+                Some(aggregated) => aggregated.add(&opening_key).unwrap(),
+                None => opening_key,
+            });
+        }
+        std::fs::write(path("aggregated_commitment.bin"), aggregated_commitment.unwrap().to_bytes()).unwrap();
+        std::fs::write(path("aggregated_opening_key.json"), aggregated_opening_key.unwrap().to_json()).unwrap();
+
+        let files = ["a.bin", "a_opening_key.json", "aggregated_commitment.bin", "aggregated_opening_key.json"];
+        let before = files.iter().map(|name| std::fs::read(path(name)).unwrap()).collect::<Vec<_>>();
+        let result = update_aggregated_commitment::<Bn254>(
+            r1cs,
+            &pk,
+            wasm,
+            &path("a.bin"),
+            &path("aggregated_commitment.bin"),
+            &path("aggregated_opening_key.json"),
+            vec!["256".to_string()],
+            ReduceMode::Strict,
+            3
+        );
+        assert!(result.is_err());
+        let after = files.iter().map(|name| std::fs::read(path(name)).unwrap()).collect::<Vec<_>>();
+        assert_eq!(before, after);
+        assert!(!std::path::Path::new(&aggregated_commitment_journal_path(&path("aggregated_commitment.bin"))).exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // allocates the values but reports other committed witnesses
+    #[derive(Clone)]
+    struct MisreportedCircuit(RangeCircuit<Fr>);
+
+    impl ConstraintSynthesizer<Fr> for MisreportedCircuit {
+        fn generate_constraints(self, cs : ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+            self.0.generate_constraints(cs)
+        }
+    }
+
+    impl CommittedCircuit<Fr> for MisreportedCircuit {
+        fn commit_witness_count(&self) -> usize {
+            self.0.commit_witness_count()
+        }
+
+        fn committed_witnesses(&self) -> Option<Vec<Fr>> {
+            self.0.committed_witnesses().map(|m| m.iter().map(|m| *m + Fr::from(1u64)).collect())
+        }
+    }
+
+    #[test]
+    fn rejects_committed_witnesses_that_do_not_open_d() {
+        let pk = range_pk(1);
+        let circuit = MisreportedCircuit(RangeCircuit::new(8, &[Fr::from(42u64)]).unwrap());
+        assert!(matches!(prove_circuit::<Bn254, _>(circuit, &pk, None, 1), Err(Error::Commitment(_))));
+    }
 }
 
@@ -0,0 +1,470 @@
+use ark_ec::pairing::Pairing;
+use ark_ff::PrimeField;
+use num_bigint::BigUint;
+use std::{collections::HashMap, fs::read, path::Path};
+
+use crate::{
+    circuit::RangeCircuit,
+    error::{Error, Result},
+    keys,
+    prover,
+    setup,
+    verifier,
+};
+
+// widths of the precompiled circuits in circom/range
+pub const RANGE_BIT_WIDTHS: [usize; 6] = [8, 16, 32, 64, 128, 252];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RangeCircuitKind {
+    // 0 <= value < 2^n
+    Bits,
+    // min <= value <= max, min and max are public inputs
+    Interval,
+}
+
+impl RangeCircuitKind {
+    fn name(&self) -> &'static str {
+        match self {
+            RangeCircuitKind::Bits => "bits",
+            RangeCircuitKind::Interval => "interval",
+        }
+    }
+}
+
+// circuit_dir : circom/bn128, circom/bls12-381 (see circom/range/build.sh)
+// key_dir     : directory holding `range_<kind>_<n>_{pk,vk}.bin`
+pub struct RangeCircuitFiles {
+    pub r1cs : String,
+    pub wasm : String,
+    pub pk : String,
+    pub vk : String,
+}
+
+pub fn range_circuit_files(
+    circuit_dir : &str,
+    key_dir : &str,
+    kind : RangeCircuitKind,
+    bit_width : usize
+) -> RangeCircuitFiles {
+    let name = format!("range_{}_{}", kind.name(), bit_width);
+    let circuit_dir = circuit_dir.trim_end_matches('/');
+    let key_dir = key_dir.trim_end_matches('/');
+
+    RangeCircuitFiles {
+        r1cs : format!("{}/{}.r1cs", circuit_dir, name),
+        wasm : format!("{}/{}.wasm", circuit_dir, name),
+        pk : format!("{}/{}_pk.bin", key_dir, name),
+        vk : format!("{}/{}_vk.bin", key_dir, name),
+    }
+}
+
+impl RangeCircuitFiles {
+    // circom/bn128 and circom/bls12-381 hold the compiled circuits (circom/range/build.sh),
+    // another circuit_dir must have them too
+    pub fn check_circuit(&self) -> Result<()> {
+        require_file(self.r1cs.as_str(), "compile the range circuits with circom/range/build.sh")?;
+        require_file(self.wasm.as_str(), "compile the range circuits with circom/range/build.sh")
+    }
+
+    pub fn check_pk(&self) -> Result<()> {
+        require_file(self.pk.as_str(), "generate the keys with setup_range_circuits")
+    }
+
+    pub fn check_vk(&self) -> Result<()> {
+        require_file(self.vk.as_str(), "generate the keys with setup_range_circuits")
+    }
+}
+
+// same checks for circuits given by path (range_vector_64x<N>, range_context_64)
+pub fn check_circuit_and_key(
+    r1cs_file_path : &str,
+    wasm_file_path : &str,
+    key_file_path : &str
+) -> Result<()> {
+    RangeCircuitFiles {
+        r1cs : r1cs_file_path.to_string(),
+        wasm : wasm_file_path.to_string(),
+        pk : key_file_path.to_string(),
+        vk : String::new(),
+    }.check_circuit()?;
+    require_file(key_file_path, "generate the key with setup_from_circom_r1cs")
+}
+
+fn require_file(path : &str, hint : &str) -> Result<()> {
+    if Path::new(&path).is_file() {
+        Ok(())
+    } else {
+        Err(Error::Circuit(format!("missing {}, {}", path, hint)))
+    }
+}
+
+// smallest supported n with  max - min < 2^n
+pub fn select_bit_width<F:PrimeField>(
+    min : F,
+    max : F
+) -> Result<usize> {
+    let min : BigUint = min.into();
+    let max : BigUint = max.into();
+    if min > max {
+        return Err(Error::InvalidRange(format!("min {} is greater than max {}", min, max)));
+    }
+
+    let span_bits = (max - min).bits() as usize;
+    RANGE_BIT_WIDTHS
+        .iter()
+        .copied()
+        .filter(|n| *n + 1 < F::MODULUS_BIT_SIZE as usize)
+        .find(|n| span_bits <= *n)
+        .ok_or_else(|| Error::InvalidRange(format!("interval needs {} bits", span_bits)))
+}
+
+// generate pk, vk of every range circuit in bit_widths
+pub fn setup_range_circuits<E:Pairing>(
+    circuit_dir : &str,
+    key_dir : &str,
+    bit_widths : &[usize],
+    seed : u64
+) -> Result<()> {
+    for (i, bit_width) in bit_widths.iter().enumerate() {
+        if !RANGE_BIT_WIDTHS.contains(bit_width) {
+            return Err(Error::InvalidRange(format!("unsupported bit width {}", bit_width)));
+        }
+        for (j, kind) in [RangeCircuitKind::Bits, RangeCircuitKind::Interval].iter().enumerate() {
+            let files = range_circuit_files(circuit_dir, key_dir, *kind, *bit_width);
+            files.check_circuit()?;
+            let proving_key = setup::setup_from_circom_r1cs_bytes::<E>(
+                &read(files.r1cs.as_str())?,
+                1,
+                seed.wrapping_add((2 * i + j) as u64)
+            )?;
+            keys::write_to_file_compresed_proving_key::<E>(
+                proving_key,
+                files.pk.as_str(),
+                files.vk.as_str()
+            )?;
+        }
+    }
+    Ok(())
+}
+
+// picks `range_interval_<n>` for the given interval and writes the proof
+// return pedersen commitment opening keys (m, v) of value
+#[allow(clippy::too_many_arguments)]
+pub fn prove_range<E:Pairing>(
+    circuit_dir : &str,
+    key_dir : &str,
+    proof_file_path : &str,
+    value : E::ScalarField,
+    min : E::ScalarField,
+    max : E::ScalarField,
+    seed : u64
+) -> Result<String> {
+    let bit_width = select_bit_width::<E::ScalarField>(min, max)?;
+
+    let value_int : BigUint = value.into();
+    let min_int : BigUint = min.into();
+    let max_int : BigUint = max.into();
+    if value_int < min_int || value_int > max_int {
+        return Err(Error::InvalidRange(format!("value is not in [{}, {}]", min_int, max_int)));
+    }
+
+    let files = range_circuit_files(circuit_dir, key_dir, RangeCircuitKind::Interval, bit_width);
+    files.check_circuit()?;
+    files.check_pk()?;
+    let mut inputs = HashMap::new();
+    inputs.insert("value".to_string(), vec![value]);
+    inputs.insert("min".to_string(), vec![min]);
+    inputs.insert("max".to_string(), vec![max]);
+
+    prover::prove::<E, _>(
+        files.r1cs.as_str(),
+        files.pk.as_str(),
+        files.wasm.as_str(),
+        proof_file_path,
+        1usize,
+        inputs,
+        seed
+    )
+}
+
+pub fn verify_range<E:Pairing>(
+    key_dir : &str,
+    proof_file_path : &str,
+    min : E::ScalarField,
+    max : E::ScalarField
+) -> Result<bool> {
+    let bit_width = select_bit_width::<E::ScalarField>(min, max)?;
+    let files = range_circuit_files("", key_dir, RangeCircuitKind::Interval, bit_width);
+    files.check_vk()?;
+
+    verifier::verify::<E>(
+        files.vk.as_str(),
+        proof_file_path,
+        vec![min, max]
+    )
+}
+
+// proves  0 <= value < 2^bit_width  with `range_bits_<bit_width>`, no public inputs
+// return pedersen commitment opening keys (m, v) of value
+pub fn prove_range_bits<E:Pairing>(
+    circuit_dir : &str,
+    key_dir : &str,
+    proof_file_path : &str,
+    value : E::ScalarField,
+    bit_width : usize,
+    seed : u64
+) -> Result<String> {
+    if !RANGE_BIT_WIDTHS.contains(&bit_width) {
+        return Err(Error::InvalidRange(format!("unsupported bit width {}", bit_width)));
+    }
+    let value_int : BigUint = value.into();
+    if value_int.bits() as usize > bit_width {
+        return Err(Error::InvalidRange(format!("{} is not less than 2^{}", value_int, bit_width)));
+    }
+
+    let files = range_circuit_files(circuit_dir, key_dir, RangeCircuitKind::Bits, bit_width);
+    files.check_circuit()?;
+    files.check_pk()?;
+    let mut inputs = HashMap::new();
+    inputs.insert("value".to_string(), vec![value]);
+
+    prover::prove::<E, _>(
+        files.r1cs.as_str(),
+        files.pk.as_str(),
+        files.wasm.as_str(),
+        proof_file_path,
+        1usize,
+        inputs,
+        seed
+    )
+}
+
+pub fn verify_range_bits<E:Pairing>(
+    key_dir : &str,
+    proof_file_path : &str,
+    bit_width : usize
+) -> Result<bool> {
+    let files = range_circuit_files("", key_dir, RangeCircuitKind::Bits, bit_width);
+    files.check_vk()?;
+
+    verifier::verify::<E>(files.vk.as_str(), proof_file_path, vec![])
+}
+
+// `range_vector_<n>x<N>` circuits, N = values.len()
+// pk must be generated with commit_witness_count = values.len()
+// return pedersen commitment opening keys ([m_0, ..., m_N-1], v)
+pub fn prove_range_vector<E:Pairing>(
+    r1cs_file_path : &str,
+    key_file_path : &str,
+    wasm_file_path : &str,
+    proof_file_path : &str,
+    values : Vec<E::ScalarField>,
+    seed : u64
+) -> Result<String> {
+    if values.is_empty() {
+        return Err(Error::InvalidRange("no values to prove".to_string()));
+    }
+    check_circuit_and_key(r1cs_file_path, wasm_file_path, key_file_path)?;
+
+    let commit_witness_count = values.len();
+    let mut inputs = HashMap::new();
+    inputs.insert("values".to_string(), values);
+
+    prover::prove::<E, _>(
+        r1cs_file_path,
+        key_file_path,
+        wasm_file_path,
+        proof_file_path,
+        commit_witness_count,
+        inputs,
+        seed
+    )
+}
+
+// native circuit::RangeCircuit, same proof and opening key files as the circom range circuits
+// but without the r1cs/wasm files, the pk is for value_count values of bit_width bits
+pub fn setup_native_range<E:Pairing>(
+    bit_width : usize,
+    value_count : usize,
+    seed : u64,
+    pk_file_path : &str,
+    vk_file_path : &str
+) -> Result<()> {
+    let circuit = RangeCircuit::<E::ScalarField>::for_setup(bit_width, value_count)?;
+    setup::setup_circuit_to_files::<E, _>(circuit, seed, pk_file_path, vk_file_path)
+}
+
+// return pedersen commitment opening keys ([m_0, ..., m_N-1], v) of values
+pub fn prove_native_range<E:Pairing>(
+    key_file_path : &str,
+    proof_file_path : &str,
+    bit_width : usize,
+    values : &[E::ScalarField],
+    seed : u64
+) -> Result<String> {
+    let circuit = RangeCircuit::<E::ScalarField>::new(bit_width, values)?;
+    prover::prove_circuit_to_file::<E, _>(circuit, key_file_path, proof_file_path, seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Bn254, Fr};
+
+    #[test]
+    fn selects_smallest_bit_width() {
+        assert_eq!(select_bit_width::<Fr>(Fr::from(18u64), Fr::from(65u64)).unwrap(), 8);
+        assert_eq!(select_bit_width::<Fr>(Fr::from(0u64), Fr::from(256u64)).unwrap(), 16);
+        assert_eq!(select_bit_width::<Fr>(Fr::from(0u64), Fr::from(u64::MAX)).unwrap(), 64);
+        assert!(matches!(select_bit_width::<Fr>(Fr::from(2u64), Fr::from(1u64)), Err(Error::InvalidRange(_))));
+    }
+
+    // proving runs the wasm witness calculator, the file checks are tested without it
+    #[test]
+    fn missing_circuit_files_are_named() {
+        let files = range_circuit_files("/nonexistent/circom/bn128", "/nonexistent/keys", RangeCircuitKind::Interval, 8);
+        let error = files.check_circuit().unwrap_err();
+        assert!(error.to_string().contains("/nonexistent/circom/bn128/range_interval_8.r1cs"), "{}", error);
+        let error = files.check_pk().unwrap_err();
+        assert!(error.to_string().contains("/nonexistent/keys/range_interval_8_pk.bin"), "{}", error);
+
+        let error = setup_range_circuits::<Bn254>("/nonexistent/circom/bn128", "/nonexistent/keys", &[64], 0).unwrap_err();
+        assert!(error.to_string().contains("range_bits_64.r1cs"), "{}", error);
+
+        let error = verify_range_bits::<Bn254>("/nonexistent/keys", "/nonexistent/proof.bin", 64).unwrap_err();
+        assert!(error.to_string().contains("/nonexistent/keys/range_bits_64_vk.bin"), "{}", error);
+
+        let error = check_circuit_and_key(
+            "/nonexistent/circom/bn128/range_vector_64x4.r1cs",
+            "/nonexistent/circom/bn128/range_vector_64x4.wasm",
+            "/nonexistent/keys/range_vector_64x4_pk.bin"
+        ).unwrap_err();
+        assert!(error.to_string().contains("range_vector_64x4.r1cs"), "{}", error);
+    }
+
+    fn key_dir(name : &str) -> String {
+        let dir = std::env::temp_dir().join(format!("range_{}_{}", std::process::id(), name));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn range_bits_circuit_proves_and_verifies() {
+        let keys = key_dir("bits");
+        setup_range_circuits::<Bn254>("circom/bn128", &keys, &[8], 0).unwrap();
+        let proof = format!("{}/proof.bin", keys);
+
+        let opening_key = prove_range_bits::<Bn254>("circom/bn128", &keys, &proof, Fr::from(255u64), 8, 1).unwrap();
+        assert!(verify_range_bits::<Bn254>(&keys, &proof, 8).unwrap());
+        std::fs::write(format!("{}/opening_key.json", keys), opening_key).unwrap();
+        let files = range_circuit_files("circom/bn128", &keys, RangeCircuitKind::Bits, 8);
+        assert!(prover::check_pedersen_commitment::<Bn254>(&files.vk, &proof, &format!("{}/opening_key.json", keys)).unwrap());
+
+        assert!(matches!(
+            prove_range_bits::<Bn254>("circom/bn128", &keys, &proof, Fr::from(256u64), 8, 1),
+            Err(Error::InvalidRange(_))
+        ));
+        std::fs::remove_dir_all(&keys).unwrap();
+    }
+
+    #[test]
+    fn range_interval_circuit_proves_and_verifies() {
+        use ark_bls12_381::{Bls12_381, Fr as Fr381};
+
+        let keys = key_dir("interval");
+        setup_range_circuits::<Bls12_381>("circom/bls12-381", &keys, &[8], 0).unwrap();
+        let proof = format!("{}/proof.bin", keys);
+        let (min, max) = (Fr381::from(18u64), Fr381::from(65u64));
+
+        prove_range::<Bls12_381>("circom/bls12-381", &keys, &proof, Fr381::from(40u64), min, max, 1).unwrap();
+        assert!(verify_range::<Bls12_381>(&keys, &proof, min, max).unwrap());
+        assert!(!verify_range::<Bls12_381>(&keys, &proof, Fr381::from(19u64), max).unwrap());
+
+        prove_range::<Bls12_381>("circom/bls12-381", &keys, &proof, max, min, max, 1).unwrap();
+        assert!(verify_range::<Bls12_381>(&keys, &proof, min, max).unwrap());
+        std::fs::remove_dir_all(&keys).unwrap();
+    }
+
+    // the witness calculator asserts the range too, whatever the checks before it
+    #[test]
+    fn range_circuit_rejects_out_of_range_witnesses() {
+        let keys = key_dir("witness");
+        setup_range_circuits::<Bn254>("circom/bn128", &keys, &[8], 0).unwrap();
+        let files = range_circuit_files("circom/bn128", &keys, RangeCircuitKind::Interval, 8);
+        let inputs = [("value", 17u64), ("min", 18), ("max", 65)]
+            .iter()
+            .map(|(name, value)| (name.to_string(), vec![Fr::from(*value)]))
+            .collect::<HashMap<_, _>>();
+
+        let result = prover::prove::<Bn254, _>(&files.r1cs, &files.pk, &files.wasm, &format!("{}/proof.bin", keys), 1, inputs, 1);
+        assert!(result.is_err());
+        std::fs::remove_dir_all(&keys).unwrap();
+    }
+
+    #[test]
+    fn range_vector_circuit_proves_and_verifies() {
+        let keys = key_dir("vector");
+        let (pk, vk, proof) = (format!("{}/pk.bin", keys), format!("{}/vk.bin", keys), format!("{}/proof.bin", keys));
+        let (r1cs, wasm) = ("circom/bn128/range_vector_64x4.r1cs", "circom/bn128/range_vector_64x4.wasm");
+        let proving_key = setup::setup_from_circom_r1cs_bytes::<Bn254>(&read(r1cs).unwrap(), 4, 0).unwrap();
+        keys::write_to_file_compresed_proving_key::<Bn254>(proving_key, &pk, &vk).unwrap();
+
+        let values = [0u64, 7, 1 << 32, u64::MAX].iter().map(|value| Fr::from(*value)).collect::<Vec<_>>();
+        prove_range_vector::<Bn254>(r1cs, &pk, wasm, &proof, values.clone(), 1).unwrap();
+        assert!(verifier::verify::<Bn254>(&vk, &proof, vec![]).unwrap());
+
+        let mut too_large = values;
+        too_large[2] = Fr::from(u64::MAX) + Fr::from(1u64);
+        assert!(prove_range_vector::<Bn254>(r1cs, &pk, wasm, &proof, too_large, 1).is_err());
+        std::fs::remove_dir_all(&keys).unwrap();
+    }
+
+    #[test]
+    fn every_range_circuit_is_shipped() {
+        for circuit_dir in ["circom/bn128", "circom/bls12-381"] {
+            for bit_width in RANGE_BIT_WIDTHS {
+                for kind in [RangeCircuitKind::Bits, RangeCircuitKind::Interval] {
+                    range_circuit_files(circuit_dir, "", kind, bit_width).check_circuit().unwrap();
+                }
+            }
+            for count in [2, 4, 8, 16, 32] {
+                for extension in ["r1cs", "wasm", "sym"] {
+                    assert!(Path::new(&format!("{}/range_vector_64x{}.{}", circuit_dir, count, extension)).is_file());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn native_range_cycle_on_bls12_377() {
+        use ark_bls12_377::{Bls12_377, Fr as Fr377};
+
+        let dir = std::env::temp_dir().join(format!("range_{}_bls12_377", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = |name : &str| dir.join(name).to_string_lossy().to_string();
+
+        setup_native_range::<Bls12_377>(8, 2, 0, &file("pk.bin"), &file("vk.bin")).unwrap();
+        let opening_key = prove_native_range::<Bls12_377>(
+            &file("pk.bin"),
+            &file("proof.bin"),
+            8,
+            &[Fr377::from(18u64), Fr377::from(255u64)],
+            1
+        ).unwrap();
+        std::fs::write(file("opening_key.json"), &opening_key).unwrap();
+
+        assert!(verifier::verify::<Bls12_377>(&file("vk.bin"), &file("proof.bin"), vec![]).unwrap());
+        assert!(prover::check_pedersen_commitment::<Bls12_377>(&file("vk.bin"), &file("proof.bin"), &file("opening_key.json")).unwrap());
+
+        let out_of_range = prove_native_range::<Bls12_377>(&file("pk.bin"), &file("proof.bin"), 8, &[Fr377::from(256u64), Fr377::from(0u64)], 1);
+        assert!(out_of_range.is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn unsupported_bit_width_is_rejected() {
+        let error = setup_range_circuits::<Bn254>("", "", &[12], 0).unwrap_err();
+        assert!(matches!(error, Error::InvalidRange(_)));
+    }
+}
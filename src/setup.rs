@@ -1,5 +1,6 @@
-use std::path::PathBuf;
-use ark_ec::{pairing::Pairing, CurveGroup};
+use std::fs::read;
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_serialize::CanonicalDeserialize;
 use ark_std::{
     rand::{rngs::StdRng, SeedableRng},
     UniformRand, 
@@ -7,13 +8,106 @@ use ark_std::{
 };
 use ark_bn254::{Bn254};
 use ark_bls12_381::Bls12_381;
-use legogroth16::{circom::CircomCircuit, ProvingKeyWithLink, ProvingKey, LinkPublicGenerators, generate_random_parameters_incl_cp_link};
+use ark_relations::r1cs::ConstraintSynthesizer;
+use blake2::Blake2b512;
+use dock_crypto_utils::hashing_utils::affine_group_elem_from_try_and_incr;
+use legogroth16::{
+    circom::CircomCircuit,
+    ProvingKeyWithLink,
+    ProvingKey,
+    LinkPublicGenerators,
+    generate_random_parameters,
+    generate_random_parameters_incl_cp_link
+};
+
+use crate::{
+    circuit::{circom_circuit_from_r1cs_bytes, CommittedCircuit},
+    error::Error,
+    keys::{write_link_keys, write_to_file_compresed_proving_key},
+};
+
+// link key, plain key and link pedersen generators made by gen_params
+pub type SetupParams<E> = (ProvingKeyWithLink<E>, ProvingKey<E>, Vec<<E as Pairing>::G1Affine>);
+
+const LINK_GENERATORS_DOMAIN : &[u8] = b"napirs-legogroth16 link generators";
+
+// how the link generators (cp_link pedersen bases, g1, g2) are chosen
+pub enum LinkGenerators<E:Pairing> {
+    // sampled from the setup rng, their discrete logs are known to whoever knows the seed
+    Seeded,
+    // hashed from a public label, anyone can re-derive them (verifier::verify_link_generators)
+    Hashed(Vec<u8>),
+    // given by the caller, e.g. the published bases of another pedersen commitment system
+    // (verifier::verify_provided_link_generators)
+    Provided(LinkPublicGenerators<E>),
+}
+
+impl<E:Pairing> LinkGenerators<E> {
+    // provided generators must have commit_witness_count + 1 pedersen bases, none of them zero
+    pub fn check(&self, commit_witness_count : usize) -> Result<(), Error> {
+        let LinkGenerators::Provided(link_gens) = self else {
+            return Ok(());
+        };
+        if link_gens.pedersen_gens.len() != commit_witness_count + 1 {
+            return Err(Error::Commitment(format!(
+                "expected {} link pedersen generators (commit_witness_count + 1), got {}",
+                commit_witness_count + 1,
+                link_gens.pedersen_gens.len()
+            )));
+        }
+        if link_gens.pedersen_gens.iter().any(|gen| gen.is_zero()) || link_gens.g1.is_zero() || link_gens.g2.is_zero() {
+            return Err(Error::Commitment("link generators must not be the point at infinity".to_string()));
+        }
+        Ok(())
+    }
+}
+
+// link generators written by keys::write_link_keys or keys::write_link_generators,
+// compressed LinkPublicGenerators : pedersen_gens (u64 length then G1 points), g1, g2
+pub fn link_generators_from_bytes<E:Pairing>(bytes : &[u8]) -> Result<LinkPublicGenerators<E>, Error> {
+    Ok(LinkPublicGenerators::<E>::deserialize_compressed(bytes)?)
+}
+
+pub fn read_link_generators_from_file<E:Pairing>(path : &str) -> Result<LinkPublicGenerators<E>, Error> {
+    link_generators_from_bytes::<E>(&read(path)?)
+}
+
+// nothing-up-my-sleeve link generators, hashed to G1/G2 from the label
+// commit_witness_count + 1 pedersen bases (the last one is for the blinding)
+pub fn link_generators_from_label<E:Pairing>(
+    label : &[u8],
+    commit_witness_count : usize
+) -> LinkPublicGenerators<E> {
+    // the label is length-prefixed so that no label is a prefix of another label's hash input
+    let label_len = (label.len() as u64).to_le_bytes();
+    let hash_input = |name : &[u8], index : u64| [LINK_GENERATORS_DOMAIN, &label_len, label, name, &index.to_le_bytes()].concat();
+    LinkPublicGenerators {
+        pedersen_gens : (0..commit_witness_count as u64 + 1)
+            .map(|i| affine_group_elem_from_try_and_incr::<E::G1Affine, Blake2b512>(&hash_input(b"pedersen", i)))
+            .collect(),
+        g1 : affine_group_elem_from_try_and_incr::<E::G1Affine, Blake2b512>(&hash_input(b"g1", 0)),
+        g2 : affine_group_elem_from_try_and_incr::<E::G2Affine, Blake2b512>(&hash_input(b"g2", 0)),
+    }
+}
+
+// works for any circuit, CircomCircuit as well as native arkworks circuits
+pub fn gen_params<E: Pairing, C: ConstraintSynthesizer<E::ScalarField> + Clone>(
+    commit_witness_count : usize,
+    circuit : C,
+    seed:u64,
+) -> Result<SetupParams<E>, Error> { 
+    gen_params_with_link_generators::<E, C>(commit_witness_count, circuit, seed, &LinkGenerators::Seeded)
+}
 
-pub fn gen_params<E: Pairing>(
+// gen_params with the link generators chosen by link_generators
+// the rng draws are the same whatever the choice, so a seed always gives the same trapdoor
+// provided generators are expected to pass LinkGenerators::check
+pub fn gen_params_with_link_generators<E: Pairing, C: ConstraintSynthesizer<E::ScalarField> + Clone>(
     commit_witness_count : usize,
-    circuit : CircomCircuit<E>,
+    circuit : C,
     seed:u64,
-) -> (ProvingKeyWithLink<E>, ProvingKey<E>, Vec<E::G1Affine>) { 
+    link_generators : &LinkGenerators<E>,
+) -> Result<SetupParams<E>, Error> { 
     
     let mut rng = StdRng::seed_from_u64(seed);
     let pedersen_gens = (0..commit_witness_count+1)
@@ -22,51 +116,113 @@ pub fn gen_params<E: Pairing>(
     let g1 = E::G1::rand(&mut rng).into_affine();
     let g2 = E::G2::rand(&mut rng).into_affine();
     
-    let link_gens: LinkPublicGenerators<E> = LinkPublicGenerators{
-        pedersen_gens,
-        g1,
-        g2,
+    let link_gens: LinkPublicGenerators<E> = match link_generators {
+        LinkGenerators::Seeded => LinkPublicGenerators{
+            pedersen_gens,
+            g1,
+            g2,
+        },
+        LinkGenerators::Hashed(label) => link_generators_from_label::<E>(label, commit_witness_count),
+        LinkGenerators::Provided(link_gens) => link_gens.clone(),
     };
 
-    // to debug
-    // println!("link gen : {:?}", link_gens);
-
     let params_link = generate_random_parameters_incl_cp_link(
         circuit.clone(), 
         link_gens.clone(), 
         commit_witness_count, 
         &mut rng
-    )
-    .unwrap();
+    )?;
+
+    let params = generate_random_parameters::<E, C, _>(circuit, commit_witness_count, &mut rng)?;
 
-    let params = circuit
-        .generate_proving_key(commit_witness_count, &mut rng)
-        .unwrap();
+    Ok((params_link, params, link_gens.pedersen_gens))
+}
+
+// setup for any circuit::CommittedCircuit (e.g. circuit::RangeCircuit or a hand-written arkworks circuit)
+pub fn setup_circuit<E:Pairing, C:CommittedCircuit<E::ScalarField>>(
+    circuit : C,
+    seed : u64
+) -> Result<ProvingKey<E>, Error> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let commit_witness_count = circuit.commit_witness_count();
+    Ok(generate_random_parameters::<E, C, _>(circuit, commit_witness_count, &mut rng)?)
+}
 
-    (params_link, params, link_gens.pedersen_gens)
+// same key files as the circom setup
+pub fn setup_circuit_to_files<E:Pairing, C:CommittedCircuit<E::ScalarField>>(
+    circuit : C,
+    seed : u64,
+    pk_file_path : &str,
+    vk_file_path : &str
+) -> Result<(), Error> {
+    let proving_key = setup_circuit::<E, C>(circuit, seed)?;
+    write_to_file_compresed_proving_key::<E>(proving_key, pk_file_path, vk_file_path)?;
+    Ok(())
 }
 
 pub fn setup_from_circom_r1cs<E:Pairing>(
     r1cs_file_path : &str,
     commit_witness_count : usize,
     seed:u64
-) -> (ProvingKeyWithLink<E>, ProvingKey<E>, Vec<E::G1Affine>){
-    let circuit: CircomCircuit<E> = CircomCircuit::<E>::from_r1cs_file(abs_path(r1cs_file_path)).unwrap();
+) -> Result<SetupParams<E>, Error> {
+    let circuit: CircomCircuit<E> = CircomCircuit::<E>::from_r1cs_file(r1cs_file_path)?;
 
     let start_time = SystemTime::now();
-    let tmp = gen_params::<E>(commit_witness_count, circuit.clone(), seed);
-    let end_time = SystemTime::now();
-    let duration = end_time.duration_since(start_time)
-        .expect("SystemTime::duration_since failed");
-    println!("setup time: {:?}", duration);
-    tmp
+    let tmp = gen_params::<E, _>(commit_witness_count, circuit, seed)?;
+    if let Ok(duration) = SystemTime::now().duration_since(start_time) {
+        log::debug!("setup time: {:?}", duration);
+    }
+    Ok(tmp)
+}
+
+// setup_from_circom_r1cs with the content of the .r1cs file, returns the proving key
+pub fn setup_from_circom_r1cs_bytes<E:Pairing>(
+    r1cs_bytes : &[u8],
+    commit_witness_count : usize,
+    seed:u64
+) -> Result<ProvingKey<E>, Error> {
+    let circuit = circom_circuit_from_r1cs_bytes::<E>(r1cs_bytes)?;
+    let (_, proving_key, _) = gen_params::<E, _>(commit_witness_count, circuit, seed)?;
+    Ok(proving_key)
+}
+
+// circom setup that also writes the link keys, which record the link generators,
+// and the link generators next to the link pk (keys::link_generators_path)
+// pk / vk are the groth16 part of the link key, the same files as setup_from_circom_r1cs
+#[allow(clippy::too_many_arguments)]
+pub fn setup_from_circom_r1cs_with_link<E:Pairing>(
+    r1cs_file_path : &str,
+    commit_witness_count : usize,
+    seed : u64,
+    link_generators : &LinkGenerators<E>,
+    pk_file_path : &str,
+    vk_file_path : &str,
+    link_pk_file_path : &str,
+    link_vk_file_path : &str
+) -> Result<(), Error> {
+    link_generators.check(commit_witness_count)?;
+    let circuit = CircomCircuit::<E>::from_r1cs_file(r1cs_file_path)?;
+    let (params_link, _, _) = gen_params_with_link_generators::<E, _>(
+        commit_witness_count,
+        circuit,
+        seed,
+        link_generators
+    )?;
+
+    let proving_key = ProvingKey {
+        vk : params_link.vk.groth16_vk.clone(),
+        common : params_link.common.clone(),
+    };
+    write_to_file_compresed_proving_key::<E>(proving_key, pk_file_path, vk_file_path)?;
+    write_link_keys::<E>(&params_link, link_pk_file_path, link_vk_file_path)?;
+    Ok(())
 }
 
 pub fn setup_from_circom_r1cs_bn128(
     r1cs_file_path : String,
     commit_witness_count : usize,
     seed:u64
-) -> (ProvingKeyWithLink<Bn254>, ProvingKey<Bn254>, Vec<ark_bn254::G1Affine>) {
+) -> Result<SetupParams<Bn254>, Error> {
     setup_from_circom_r1cs::<Bn254>(r1cs_file_path.as_str(), commit_witness_count, seed)
 }
 
@@ -74,12 +230,25 @@ pub fn setup_from_circom_r1cs_bls12_381(
     r1cs_file_path : String,
     commit_witness_count : usize,
     seed:u64
-) -> (ProvingKeyWithLink<Bls12_381>, ProvingKey<Bls12_381>, Vec<ark_bls12_381::G1Affine>) {
+) -> Result<SetupParams<Bls12_381>, Error> {
     setup_from_circom_r1cs::<Bls12_381>(r1cs_file_path.as_str(), commit_witness_count, seed)
 }
 
-pub fn abs_path(relative_path: &str) -> String {
-    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    path.push(relative_path);
-    path.to_string_lossy().to_string()
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn label_is_length_prefixed() {
+        let link_gens = link_generators_from_label::<Bn254>(b"label", 2);
+        assert_eq!(link_gens.pedersen_gens.len(), 3);
+
+        let hash_input = [LINK_GENERATORS_DOMAIN, &5u64.to_le_bytes(), b"label", b"pedersen", &1u64.to_le_bytes()].concat();
+        assert_eq!(
+            link_gens.pedersen_gens[1],
+            affine_group_elem_from_try_and_incr::<ark_bn254::G1Affine, Blake2b512>(&hash_input)
+        );
+        assert!(link_generators_from_label::<Bn254>(b"label", 2) == link_gens);
+        assert!(link_generators_from_label::<Bn254>(b"labe", 2).g1 != link_gens.g1);
+    }
+}
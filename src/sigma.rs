@@ -0,0 +1,378 @@
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_serialize::{CanonicalSerialize, CanonicalDeserialize};
+use ark_std::{
+    rand::{rngs::StdRng, SeedableRng},
+    UniformRand,
+};
+use hex::ToHex;
+use dock_crypto_utils::transcript::{new_merlin_transcript, Transcript};
+
+use crate::error::Error;
+use crate::field::{field_element_to_hex, parse_hex_field_element, ReduceMode};
+
+// proof of knowledge of the blinding of an aggregated commitment, see verifier::verify_aggregated_total
+pub const AGGREGATED_TOTAL_LABEL : &[u8] = b"napirs-legogroth16 aggregated total";
+
+// equal messages in proof.d and a commitment under other bases, see prove_commitment_equality
+pub const COMMITMENT_EQUALITY_LABEL : &[u8] = b"napirs-legogroth16 commitment equality";
+
+// knowledge of the opening {m, v} of proof.d or an aggregated commitment
+pub const OPENING_LABEL : &[u8] = b"napirs-legogroth16 opening";
+
+// bases of a pedersen commitment  g_0*m_0 + ... + g_n-1*m_n-1 + h*v
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PedersenBases<G:AffineRepr> {
+    pub g : Vec<G>,
+    pub h : G,
+}
+
+impl<G:AffineRepr> PedersenBases<G> {
+    pub fn new(g : Vec<G>, h : G) -> Self {
+        Self { g, h }
+    }
+
+    pub fn commit(
+        &self,
+        m : &[G::ScalarField],
+        v : &G::ScalarField
+    ) -> Result<G, Error> {
+        self.check_message_len(m.len())?;
+        Ok((G::Group::msm_unchecked(&self.g, m) + self.h * v).into_affine())
+    }
+
+    // g_0, ..., g_k-1, h  for the first k = message_count messages
+    pub fn opening_bases(&self, message_count : usize) -> Result<Vec<G>, Error> {
+        if message_count == 0 || message_count > self.g.len() {
+            return Err(Error::Commitment(format!(
+                "expected 1 to {} committed witnesses, got {}",
+                self.g.len(),
+                message_count
+            )));
+        }
+        let mut opening_bases = self.g[..message_count].to_vec();
+        opening_bases.push(self.h);
+        Ok(opening_bases)
+    }
+
+    fn check_message_len(&self, len : usize) -> Result<(), Error> {
+        if self.g.len() != len {
+            return Err(Error::Commitment(format!(
+                "{} bases and {} committed messages",
+                self.g.len(),
+                len
+            )));
+        }
+        Ok(())
+    }
+}
+
+// non-interactive schnorr proof of knowledge of x_0, ..., x_n-1 with
+//   y = b_0*x_0 + ... + b_n-1*x_n-1
+// prover : t = sum b_i*r_i, c = H(label, context, bases, y, t), z_i = r_i + c*x_i
+// verify : sum b_i*z_i == t + c*y
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SchnorrProof<G:AffineRepr> {
+    pub t : G,
+    pub responses : Vec<G::ScalarField>,
+}
+
+impl<G:AffineRepr> SchnorrProof<G> {
+    // label separates the statements, a proof for one label does not verify for another
+    // context binds the proof to the caller's session (e.g. a nonce), so it can not be replayed
+    pub fn prove(
+        label : &'static [u8],
+        context : &[u8],
+        bases : &[G],
+        y : &G,
+        witnesses : &[G::ScalarField]
+    ) -> Result<Self, Error> {
+        if bases.len() != witnesses.len() {
+            return Err(Error::Verification(format!(
+                "{} bases and {} witnesses",
+                bases.len(),
+                witnesses.len()
+            )));
+        }
+
+        // fresh randomness, a reused blinding reveals the witnesses
+        let mut rng = StdRng::from_entropy();
+        let blindings = (0..bases.len())
+            .map(|_| G::ScalarField::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let t = G::Group::msm_unchecked(bases, &blindings).into_affine();
+
+        let c = challenge::<G>(label, context, bases, y, &t);
+        let responses = blindings
+            .iter()
+            .zip(witnesses)
+            .map(|(r, x)| *r + c * x)
+            .collect();
+
+        Ok(Self { t, responses })
+    }
+
+    pub fn verify(
+        &self,
+        label : &'static [u8],
+        context : &[u8],
+        bases : &[G],
+        y : &G
+    ) -> bool {
+        if bases.len() != self.responses.len() {
+            return false;
+        }
+        let c = challenge::<G>(label, context, bases, y, &self.t);
+        G::Group::msm_unchecked(bases, &self.responses) == self.t + *y * c
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut compressed_bytes:Vec<u8> = Vec::new();
+        self.serialize_compressed(&mut compressed_bytes).unwrap();
+        compressed_bytes
+    }
+
+    pub fn from_bytes(bytes : &[u8]) -> Result<Self, Error> {
+        Ok(Self::deserialize_compressed(bytes)?)
+    }
+
+    // {"t" : hex compressed point, "responses" : [hex, ...]}
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&serde_json::json!({
+            "t" : point_to_hex(&self.t),
+            "responses" : self.responses.iter().map(field_element_to_hex).collect::<Vec<_>>()
+        })).unwrap()
+    }
+
+    pub fn from_json(json : &str) -> Result<Self, Error> {
+        let proof_json : serde_json::Value = serde_json::from_str(json)
+            .map_err(|e| Error::Verification(e.to_string()))?;
+
+        let t = point_from_json::<G>(&proof_json["t"], "t")?;
+        let responses = scalars_from_json::<G>(&proof_json["responses"], "responses")?;
+
+        Ok(Self { t, responses })
+    }
+}
+
+fn point_to_hex<G:AffineRepr>(point : &G) -> String {
+    let mut bytes:Vec<u8> = Vec::new();
+    point.serialize_compressed(&mut bytes).unwrap();
+    bytes.encode_hex::<String>()
+}
+
+fn point_from_json<G:AffineRepr>(value : &serde_json::Value, name : &str) -> Result<G, Error> {
+    let point = value
+        .as_str()
+        .ok_or_else(|| Error::Verification(format!("proof without {}", name)))?;
+    Ok(G::deserialize_compressed(
+        &*hex::decode(point).map_err(|e| Error::Verification(e.to_string()))?
+    )?)
+}
+
+fn scalars_from_json<G:AffineRepr>(value : &serde_json::Value, name : &str) -> Result<Vec<G::ScalarField>, Error> {
+    value
+        .as_array()
+        .ok_or_else(|| Error::Verification(format!("proof without {}", name)))?
+        .iter()
+        .map(|scalar| match scalar.as_str() {
+            Some(scalar) => Ok(parse_hex_field_element(scalar, ReduceMode::Strict)?),
+            None => Err(Error::Verification(format!("expected hex string, got {}", scalar))),
+        })
+        .collect()
+}
+
+// fiat-shamir challenge over the whole statement
+fn challenge<G:AffineRepr>(
+    label : &'static [u8],
+    context : &[u8],
+    bases : &[G],
+    y : &G,
+    t : &G
+) -> G::ScalarField {
+    let mut transcript = new_merlin_transcript(label);
+    transcript.append(b"context", &context.to_vec());
+    transcript.append(b"bases", &bases.to_vec());
+    transcript.append(b"y", y);
+    transcript.append(b"t", t);
+    transcript.challenge_scalar(b"challenge")
+}
+
+// non-interactive proof that two pedersen commitments under different bases commit to the same messages
+//   y_1 = g_1*m + h_1*v_1,  y_2 = g_2*m + h_2*v_2
+// prover : t_k = g_k*r + h_k*s_k, c = H(label, bases, y_1, y_2, t_1, t_2)
+//          z_m = r + c*m, z_k = s_k + c*v_k
+// verify : g_k*z_m + h_k*z_k == t_k + c*y_k  for k = 1, 2
+// the shared response z_m binds the messages of both commitments, the blindings stay independent
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct EqualityProof<G:AffineRepr> {
+    pub t : [G; 2],
+    pub m_responses : Vec<G::ScalarField>,
+    pub v_responses : [G::ScalarField; 2],
+}
+
+impl<G:AffineRepr> EqualityProof<G> {
+    pub fn prove(
+        label : &'static [u8],
+        bases : [&PedersenBases<G>; 2],
+        m : &[G::ScalarField],
+        v : [G::ScalarField; 2]
+    ) -> Result<Self, Error> {
+        let y = [bases[0].commit(m, &v[0])?, bases[1].commit(m, &v[1])?];
+
+        let mut rng = StdRng::from_entropy();
+        let m_blindings = m
+            .iter()
+            .map(|_| G::ScalarField::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let v_blindings = [G::ScalarField::rand(&mut rng), G::ScalarField::rand(&mut rng)];
+        let t = [
+            bases[0].commit(&m_blindings, &v_blindings[0])?,
+            bases[1].commit(&m_blindings, &v_blindings[1])?,
+        ];
+
+        let c = equality_challenge::<G>(label, bases, &y, &t);
+        let m_responses = m_blindings
+            .iter()
+            .zip(m)
+            .map(|(r, m)| *r + c * m)
+            .collect();
+        let v_responses = [v_blindings[0] + c * v[0], v_blindings[1] + c * v[1]];
+
+        Ok(Self { t, m_responses, v_responses })
+    }
+
+    pub fn verify(
+        &self,
+        label : &'static [u8],
+        bases : [&PedersenBases<G>; 2],
+        y : [G; 2]
+    ) -> bool {
+        let c = equality_challenge::<G>(label, bases, &y, &self.t);
+        (0..2).all(|k| match bases[k].commit(&self.m_responses, &self.v_responses[k]) {
+            Ok(lhs) => lhs.into_group() == self.t[k] + y[k] * c,
+            Err(_) => false,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut compressed_bytes:Vec<u8> = Vec::new();
+        self.serialize_compressed(&mut compressed_bytes).unwrap();
+        compressed_bytes
+    }
+
+    pub fn from_bytes(bytes : &[u8]) -> Result<Self, Error> {
+        Ok(Self::deserialize_compressed(bytes)?)
+    }
+
+    // {"t" : [hex, hex], "m_responses" : [hex, ...], "v_responses" : [hex, hex]}
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&serde_json::json!({
+            "t" : self.t.iter().map(point_to_hex).collect::<Vec<_>>(),
+            "m_responses" : self.m_responses.iter().map(field_element_to_hex).collect::<Vec<_>>(),
+            "v_responses" : self.v_responses.iter().map(field_element_to_hex).collect::<Vec<_>>()
+        })).unwrap()
+    }
+
+    pub fn from_json(json : &str) -> Result<Self, Error> {
+        let proof_json : serde_json::Value = serde_json::from_str(json)
+            .map_err(|e| Error::Verification(e.to_string()))?;
+
+        let t = match proof_json["t"].as_array() {
+            Some(t) if t.len() == 2 => [point_from_json::<G>(&t[0], "t")?, point_from_json::<G>(&t[1], "t")?],
+            _ => return Err(Error::Verification("expected 2 points in t".to_string())),
+        };
+        let m_responses = scalars_from_json::<G>(&proof_json["m_responses"], "m_responses")?;
+        let v_responses = match scalars_from_json::<G>(&proof_json["v_responses"], "v_responses")?[..] {
+            [v_1, v_2] => [v_1, v_2],
+            _ => return Err(Error::Verification("expected 2 v_responses".to_string())),
+        };
+
+        Ok(Self { t, m_responses, v_responses })
+    }
+}
+
+fn equality_challenge<G:AffineRepr>(
+    label : &'static [u8],
+    bases : [&PedersenBases<G>; 2],
+    y : &[G; 2],
+    t : &[G; 2]
+) -> G::ScalarField {
+    let mut transcript = new_merlin_transcript(label);
+    transcript.append(b"bases_1", bases[0]);
+    transcript.append(b"bases_2", bases[1]);
+    transcript.append(b"y_1", &y[0]);
+    transcript.append(b"y_2", &y[1]);
+    transcript.append(b"t_1", &t[0]);
+    transcript.append(b"t_2", &t[1]);
+    transcript.challenge_scalar(b"challenge")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Affine};
+    use ark_ec::CurveGroup;
+
+    const LABEL : &[u8] = b"test";
+
+    fn statement() -> (Vec<G1Affine>, Vec<Fr>, G1Affine) {
+        let mut rng = StdRng::seed_from_u64(0);
+        let bases = (0..3).map(|_| G1Affine::rand(&mut rng)).collect::<Vec<_>>();
+        let witnesses = (0..3).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let y = <G1Affine as AffineRepr>::Group::msm_unchecked(&bases, &witnesses).into_affine();
+        (bases, witnesses, y)
+    }
+
+    #[test]
+    fn schnorr_round_trip() {
+        let (bases, witnesses, y) = statement();
+        let proof = SchnorrProof::prove(LABEL, b"nonce", &bases, &y, &witnesses).unwrap();
+
+        assert!(proof.verify(LABEL, b"nonce", &bases, &y));
+        assert_eq!(SchnorrProof::<G1Affine>::from_bytes(&proof.to_bytes()).unwrap(), proof);
+        assert_eq!(SchnorrProof::<G1Affine>::from_json(&proof.to_json()).unwrap(), proof);
+    }
+
+    #[test]
+    fn tampered_schnorr_proof_fails() {
+        let (bases, witnesses, y) = statement();
+        let proof = SchnorrProof::prove(LABEL, b"nonce", &bases, &y, &witnesses).unwrap();
+
+        let mut tampered = proof.clone();
+        tampered.responses[1] += Fr::from(1u64);
+        assert!(!tampered.verify(LABEL, b"nonce", &bases, &y));
+
+        let mut tampered = proof.clone();
+        tampered.responses.pop();
+        assert!(!tampered.verify(LABEL, b"nonce", &bases, &y));
+
+        assert!(!proof.verify(b"other", b"nonce", &bases, &y));
+        assert!(!proof.verify(LABEL, b"replayed", &bases, &y));
+        assert!(!proof.verify(LABEL, b"nonce", &bases, &bases[0]));
+    }
+
+    #[test]
+    fn equality_round_trip() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut bases = || PedersenBases::new((0..2).map(|_| G1Affine::rand(&mut rng)).collect(), G1Affine::rand(&mut rng));
+        let bases = [bases(), bases()];
+        let (m, v) = ([Fr::from(42u64), Fr::from(7u64)], [Fr::from(3u64), Fr::from(5u64)]);
+        let y = [bases[0].commit(&m, &v[0]).unwrap(), bases[1].commit(&m, &v[1]).unwrap()];
+        let proof = EqualityProof::prove(LABEL, [&bases[0], &bases[1]], &m, v).unwrap();
+
+        let restored = EqualityProof::<G1Affine>::from_json(&proof.to_json()).unwrap();
+        assert_eq!(restored, proof);
+        assert!(restored.verify(LABEL, [&bases[0], &bases[1]], y));
+        assert_eq!(EqualityProof::<G1Affine>::from_bytes(&proof.to_bytes()).unwrap(), proof);
+
+        let mut json : serde_json::Value = serde_json::from_str(&proof.to_json()).unwrap();
+        json["v_responses"].as_array_mut().unwrap().pop();
+        assert!(EqualityProof::<G1Affine>::from_json(&json.to_string()).is_err());
+    }
+
+    #[test]
+    fn schnorr_needs_a_witness_per_base() {
+        let (bases, witnesses, y) = statement();
+        assert!(SchnorrProof::prove(LABEL, b"", &bases[..2], &y, &witnesses).is_err());
+    }
+}
@@ -0,0 +1,189 @@
+use ark_ec::pairing::Pairing;
+use std::{
+    collections::HashMap,
+    fs::read_to_string,
+};
+
+use crate::{
+    commitment::OpeningKey,
+    error::{Error, Result},
+    field::field_element_to_hex,
+};
+
+// signals of a circom .sym file (`circom --sym`), one line per signal
+//   label_index,wire_index,component_index,name      e.g. 3,2,0,main.value
+// wire_index is -1 for signals removed by the circom optimizer
+//
+// circom orders the wires as : constant 1, outputs and public inputs of main,
+// private inputs of main, then every other signal.
+// proof.d commits to the commit_witness_count wires after the public ones
+pub struct SymbolTable {
+    wires : HashMap<String, i64>,
+}
+
+impl SymbolTable {
+    pub fn parse(sym : &str) -> Result<Self> {
+        let mut wires = HashMap::new();
+        for (line_number, line) in sym.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+            let fields = line.trim().splitn(4, ',').collect::<Vec<_>>();
+            let wire = match fields.as_slice() {
+                [_, wire, _, _] => wire.parse::<i64>().ok(),
+                _ => None,
+            };
+            let wire = wire.ok_or_else(|| Error::Circuit(format!(
+                "invalid .sym line {} : {}",
+                line_number + 1,
+                line
+            )))?;
+            wires.insert(fields[3].to_string(), wire);
+        }
+        Ok(Self { wires })
+    }
+
+    pub fn read_from_file(path : &str) -> Result<Self> {
+        Self::parse(&read_to_string(path)?)
+    }
+
+    // name with or without the "main." prefix
+    pub fn wire(&self, name : &str) -> Result<usize> {
+        let name = full_name(name);
+        match self.wires.get(&name) {
+            Some(wire) if *wire >= 0 => Ok(*wire as usize),
+            Some(_) => Err(Error::Circuit(format!(
+                "signal {} was removed by the circom optimizer, use it in a constraint or compile with --O0",
+                name
+            ))),
+            None => Err(Error::Circuit(format!("signal {} is not in the .sym file", name))),
+        }
+    }
+
+    // the signals must be the committed witnesses of proof.d, in this order
+    // signals[i] must be wire 1 + public_inputs_count + i
+    pub fn check_committed_signals(
+        &self,
+        signals : &[String],
+        public_inputs_count : usize
+    ) -> Result<()> {
+        let first = 1 + public_inputs_count;
+        for (i, signal) in signals.iter().enumerate() {
+            let wire = self.wire(signal)?;
+            if wire != first + i {
+                return Err(Error::Circuit(format!(
+                    "signal {} is wire {} but committed witness {} is wire {} ({}). \
+                     circom puts the private inputs of main right after the public signals, \
+                     declare the committed signals ({}) first among the private inputs of main, in this order",
+                    full_name(signal),
+                    wire,
+                    i,
+                    first + i,
+                    self.signal_at(first + i).map_or("no signal".to_string(), |name| format!("now {}", name)),
+                    signals.iter().map(|signal| full_name(signal)).collect::<Vec<_>>().join(", ")
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    // signals declared in main, arrays by name without the index
+    pub fn main_signals(&self) -> Vec<String> {
+        let mut names = self.wires
+            .keys()
+            .filter_map(|name| name.strip_prefix("main."))
+            .filter(|name| !name.contains('.'))
+            .map(|name| name.split('[').next().unwrap().to_string())
+            .collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    // several names can share a wire (aliases), the shortest is the declared one
+    fn signal_at(&self, wire : usize) -> Option<&str> {
+        self.wires
+            .iter()
+            .filter(|(_, w)| **w == wire as i64)
+            .map(|(name, _)| name.as_str())
+            .min_by_key(|name| (name.len(), *name))
+    }
+}
+
+fn full_name(name : &str) -> String {
+    if name.starts_with("main.") {
+        name.to_string()
+    } else {
+        format!("main.{}", name)
+    }
+}
+
+// opening key of proof.d with the name of every committed signal
+pub struct SignalOpening<E:Pairing> {
+    pub signals : Vec<String>,
+    pub opening_key : OpeningKey<E>,
+}
+
+impl<E:Pairing> SignalOpening<E> {
+    // opening key json with the committed values by signal name
+    // {"m" : [hex, ...], "v" : hex, "signals" : {"main.value" : hex, ...}}
+    // readable by OpeningKey::from_json
+    pub fn to_json(&self) -> String {
+        let signals = self.signals
+            .iter()
+            .zip(self.opening_key.m.iter())
+            .map(|(signal, m)| (full_name(signal), serde_json::json!(field_element_to_hex(m))))
+            .collect::<serde_json::Map<_, _>>();
+        serde_json::to_string(&serde_json::json!({
+            "m" : self.opening_key.m.iter().map(field_element_to_hex).collect::<Vec<_>>(),
+            "v" : field_element_to_hex(&self.opening_key.v),
+            "signals" : signals
+        })).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // circom --sym output of a circuit with a public limit, private value and other,
+    // and a LessThan component whose input wire the optimizer removed
+    const SYM : &str = "1,1,0,main.limit
+2,3,0,main.value
+3,2,0,main.other
+4,-1,1,main.lt.in[0]
+5,3,1,main.lt.in[1]
+6,4,1,main.lt.out
+";
+
+    #[test]
+    fn parses_a_sym_file() {
+        let sym = SymbolTable::parse(SYM).unwrap();
+        assert_eq!(sym.wire("value").unwrap(), 3);
+        assert_eq!(sym.wire("main.other").unwrap(), 2);
+        assert_eq!(sym.main_signals(), vec!["limit", "other", "value"]);
+        // the declared name of an aliased wire
+        assert_eq!(sym.signal_at(3), Some("main.value"));
+    }
+
+    #[test]
+    fn removed_and_unknown_signals() {
+        let sym = SymbolTable::parse(SYM).unwrap();
+        assert!(matches!(sym.wire("lt.in[0]"), Err(Error::Circuit(message)) if message.contains("removed by the circom optimizer")));
+        assert!(matches!(sym.wire("total"), Err(Error::Circuit(message)) if message.contains("main.total is not in the .sym file")));
+        assert!(matches!(SymbolTable::parse("1,2,main.value"), Err(Error::Circuit(message)) if message.contains("invalid .sym line 1")));
+    }
+
+    #[test]
+    fn committed_signals_must_follow_the_public_ones() {
+        let sym = SymbolTable::parse(SYM).unwrap();
+        assert!(sym.check_committed_signals(&["other".to_string(), "value".to_string()], 1).is_ok());
+
+        match sym.check_committed_signals(&["value".to_string()], 1) {
+            Err(Error::Circuit(message)) => assert_eq!(
+                message,
+                "signal main.value is wire 3 but committed witness 0 is wire 2 (now main.other). \
+                 circom puts the private inputs of main right after the public signals, \
+                 declare the committed signals (main.value) first among the private inputs of main, in this order"
+            ),
+            other => panic!("expected a reorder error, got {:?}", other.err()),
+        }
+    }
+}
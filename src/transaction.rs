@@ -0,0 +1,254 @@
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{Error, Result},
+};
+
+// write-temp-then-rename transaction over several files
+//
+// commit
+//  1. journal <- {"state" : "prepare", files}
+//  2. every file is written to `<target>.tmp` and synced
+//  3. journal <- {"state" : "commit", files}     (commit point)
+//  4. every `<target>.tmp` is renamed over `<target>`
+//  5. journal is removed
+//
+// recover, after a crash at any step
+//  no journal        : nothing to do
+//  "prepare" journal : the targets were never touched, tmp files are removed (roll back)
+//  "commit" journal  : remaining tmp files are renamed (roll forward)
+pub struct FileTransaction {
+    journal_path : PathBuf,
+    files : Vec<(PathBuf, Vec<u8>)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Recovery {
+    Clean,
+    RolledBack,
+    Completed,
+}
+
+impl Recovery {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Recovery::Clean => "clean",
+            Recovery::RolledBack => "rolled_back",
+            Recovery::Completed => "completed",
+        }
+    }
+}
+
+impl FileTransaction {
+    pub fn new(journal_path : &str) -> Self {
+        Self {
+            journal_path : PathBuf::from(journal_path),
+            files : Vec::new(),
+        }
+    }
+
+    // staged only, nothing is written before commit
+    pub fn write<C: Into<Vec<u8>>>(&mut self, path : &str, contents : C) {
+        self.files.push((PathBuf::from(path), contents.into()));
+    }
+
+    pub fn commit(self) -> Result<()> {
+        let targets = self.files.iter().map(|(target, _)| target.clone()).collect::<Vec<_>>();
+
+        write_journal(&self.journal_path, "prepare", &targets)?;
+        for (target, contents) in self.files.iter() {
+            write_synced(&tmp_path(target), contents)?;
+        }
+        write_journal(&self.journal_path, "commit", &targets)?;
+
+        roll_forward(&targets)?;
+        remove_journal(&self.journal_path)
+    }
+}
+
+pub fn recover(journal_path : &str) -> Result<Recovery> {
+    let journal_path = PathBuf::from(journal_path);
+    if !journal_path.exists() {
+        return Ok(Recovery::Clean);
+    }
+
+    let (state, targets) = read_journal(&journal_path)?;
+    let recovery = match state.as_str() {
+        "prepare" => {
+            for target in targets.iter() {
+                let tmp = tmp_path(target);
+                if tmp.exists() {
+                    fs::remove_file(tmp)?;
+                }
+            }
+            Recovery::RolledBack
+        },
+        "commit" => {
+            roll_forward(&targets)?;
+            Recovery::Completed
+        },
+        state => return Err(Error::Transaction(format!("unknown journal state {}", state))),
+    };
+
+    remove_journal(&journal_path)?;
+    Ok(recovery)
+}
+
+fn roll_forward(targets : &[PathBuf]) -> Result<()> {
+    for target in targets.iter() {
+        let tmp = tmp_path(target);
+        // already renamed before the crash
+        if tmp.exists() {
+            fs::rename(&tmp, target)?;
+        }
+        sync_parent_dir(target);
+    }
+    Ok(())
+}
+
+fn tmp_path(target : &Path) -> PathBuf {
+    let mut tmp = target.as_os_str().to_owned();
+    tmp.push(".tmp");
+    PathBuf::from(tmp)
+}
+
+// the journal itself is replaced atomically
+fn write_journal(journal_path : &Path, state : &str, targets : &[PathBuf]) -> Result<()> {
+    let journal = serde_json::json!({
+        "state" : state,
+        "files" : targets.iter().map(|target| target.to_string_lossy()).collect::<Vec<_>>()
+    });
+    let tmp = tmp_path(journal_path);
+    write_synced(&tmp, journal.to_string().as_bytes())?;
+    fs::rename(&tmp, journal_path)?;
+    sync_parent_dir(journal_path);
+    Ok(())
+}
+
+fn read_journal(journal_path : &Path) -> Result<(String, Vec<PathBuf>)> {
+    let journal : serde_json::Value = serde_json::from_slice(&fs::read(journal_path)?)
+        .map_err(|e| Error::Transaction(format!("corrupted journal {} : {}", journal_path.display(), e)))?;
+
+    let state = journal["state"]
+        .as_str()
+        .ok_or_else(|| Error::Transaction("journal without state".to_string()))?
+        .to_string();
+    let targets = journal["files"]
+        .as_array()
+        .ok_or_else(|| Error::Transaction("journal without files".to_string()))?
+        .iter()
+        .map(|target| target.as_str().map(PathBuf::from))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| Error::Transaction("journal with invalid file".to_string()))?;
+    Ok((state, targets))
+}
+
+fn remove_journal(journal_path : &Path) -> Result<()> {
+    fs::remove_file(journal_path)?;
+    sync_parent_dir(journal_path);
+    Ok(())
+}
+
+fn write_synced(path : &Path, contents : &[u8]) -> Result<()> {
+    let mut file = File::create(path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    Ok(())
+}
+
+// makes renames durable, not supported on every platform so errors are ignored
+fn sync_parent_dir(path : &Path) {
+    if let Some(dir) = path.parent() {
+        if let Ok(dir) = File::open(dir) {
+            let _ = dir.sync_all();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // fresh directory per test, the tests run in parallel
+    fn test_dir(name : &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("transaction_{}_{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn path(dir : &Path, name : &str) -> String {
+        dir.join(name).to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn commit_replaces_every_file() {
+        let dir = test_dir("commit");
+        let journal = path(&dir, "journal");
+        fs::write(path(&dir, "a"), "old a").unwrap();
+
+        let mut txn = FileTransaction::new(&journal);
+        txn.write(&path(&dir, "a"), "new a");
+        txn.write(&path(&dir, "b"), "new b");
+        txn.commit().unwrap();
+
+        assert_eq!(fs::read_to_string(path(&dir, "a")).unwrap(), "new a");
+        assert_eq!(fs::read_to_string(path(&dir, "b")).unwrap(), "new b");
+        assert_eq!(recover(&journal).unwrap(), Recovery::Clean);
+    }
+
+    #[test]
+    fn interrupted_before_commit_point_rolls_back() {
+        let dir = test_dir("prepare");
+        let journal = path(&dir, "journal");
+        let targets = [dir.join("a"), dir.join("b")];
+        fs::write(&targets[0], "old a").unwrap();
+        fs::write(&targets[1], "old b").unwrap();
+
+        // crash while writing the tmp files
+        write_journal(Path::new(&journal), "prepare", &targets).unwrap();
+        write_synced(&tmp_path(&targets[0]), b"new a").unwrap();
+
+        assert_eq!(recover(&journal).unwrap(), Recovery::RolledBack);
+        assert_eq!(fs::read_to_string(&targets[0]).unwrap(), "old a");
+        assert_eq!(fs::read_to_string(&targets[1]).unwrap(), "old b");
+        assert!(!tmp_path(&targets[0]).exists());
+        assert!(!Path::new(&journal).exists());
+    }
+
+    #[test]
+    fn interrupted_after_commit_point_completes() {
+        let dir = test_dir("rename");
+        let journal = path(&dir, "journal");
+        let targets = [dir.join("a"), dir.join("b")];
+        fs::write(&targets[0], "old a").unwrap();
+        fs::write(&targets[1], "old b").unwrap();
+
+        // crash after renaming the first file
+        write_journal(Path::new(&journal), "prepare", &targets).unwrap();
+        write_synced(&tmp_path(&targets[0]), b"new a").unwrap();
+        write_synced(&tmp_path(&targets[1]), b"new b").unwrap();
+        write_journal(Path::new(&journal), "commit", &targets).unwrap();
+        fs::rename(tmp_path(&targets[0]), &targets[0]).unwrap();
+
+        assert_eq!(recover(&journal).unwrap(), Recovery::Completed);
+        assert_eq!(fs::read_to_string(&targets[0]).unwrap(), "new a");
+        assert_eq!(fs::read_to_string(&targets[1]).unwrap(), "new b");
+        assert_eq!(recover(&journal).unwrap(), Recovery::Clean);
+    }
+
+    #[test]
+    fn corrupted_journal_is_an_error() {
+        let dir = test_dir("corrupted");
+        let journal = path(&dir, "journal");
+        fs::write(&journal, "{\"state\" : \"prepare\"").unwrap();
+        assert!(matches!(recover(&journal), Err(Error::Transaction(_))));
+
+        fs::write(&journal, "{\"state\" : \"done\", \"files\" : []}").unwrap();
+        assert!(matches!(recover(&journal), Err(Error::Transaction(_))));
+    }
+}
@@ -1,12 +1,20 @@
 use ark_ec::pairing::Pairing;
-use legogroth16::{VerifyingKey, prepare_verifying_key, verify_proof, Proof};
+use legogroth16::{LinkPublicGenerators, VerifyingKey, VerifyingKeyWithLink, prepare_verifying_key, verify_proof, Proof};
 use ark_serialize::CanonicalDeserialize;
 use std::{
     fs::read,
     time::SystemTime
 };
 
-use crate::keys::abs_path;
+use crate::keys::{link_generators_of, read_link_verifying_key_from_file};
+use crate::context::context_to_field;
+use crate::commitment::Commitment;
+use crate::error::{Error, Result};
+use crate::prover::{calculate_pedersen_commitment, check_aggregate_limit, pedersen_bases};
+use crate::setup::link_generators_from_label;
+use crate::sigma::{
+    EqualityProof, PedersenBases, SchnorrProof, AGGREGATED_TOTAL_LABEL, COMMITMENT_EQUALITY_LABEL, OPENING_LABEL
+};
 
 pub fn verify<
     E : Pairing,
@@ -14,21 +22,21 @@ pub fn verify<
     vk_path : &str,
     proof_path : &str,
     public_inputs : Vec<E::ScalarField>
-) -> bool {
-    let vk_bin = read(abs_path(vk_path)).unwrap();
-    let pk_bin = read(abs_path(proof_path)).unwrap();
+) -> Result<bool> {
+    let vk_bin = read(vk_path)?;
+    let pk_bin = read(proof_path)?;
 
     let verifing_key = VerifyingKey::<E>::deserialize_compressed(&*vk_bin);
     let verifing_key = match verifing_key { 
         Ok(verifing_key) => verifing_key,
-        Err(_e) => return false
+        Err(_e) => return Ok(false)
     };
     let prepared_vk = prepare_verifying_key::<E>(&verifing_key);
 
     let proof = Proof::<E>::deserialize_compressed(&*pk_bin);
     let proof = match proof{
         Ok(proof) => proof,
-        Err(_e) => return false
+        Err(_e) => return Ok(false)
     };
 
     let verify_start_time = SystemTime::now();
@@ -36,10 +44,364 @@ pub fn verify<
     let verify_end_time = SystemTime::now();
     let verify_duration = verify_end_time.duration_since(verify_start_time)
         .expect("SystemTime::duration_since failed");
-    println!("verify time: {:?}", verify_duration);
+    log::debug!("verify time: {:?}", verify_duration);
     
-    match ver {
-        Ok(()) => return true,
-        Err(_e) => return false
+    Ok(ver.is_ok())
+}
+
+// in memory version of verify, for proofs of any circuit (circom or circuit::CommittedCircuit)
+pub fn verify_circuit_proof<E:Pairing>(
+    vk : &VerifyingKey<E>,
+    proof : &Proof<E>,
+    public_inputs : &[E::ScalarField]
+) -> bool {
+    verify_proof(&prepare_verifying_key::<E>(vk), proof, public_inputs).is_ok()
+}
+
+// verify for circuits with a context input, which must be the last public input
+// public_inputs : the other public inputs
+pub fn verify_with_context<E:Pairing>(
+    vk_path : &str,
+    proof_path : &str,
+    mut public_inputs : Vec<E::ScalarField>,
+    context : &[u8]
+) -> Result<bool> {
+    public_inputs.push(context_to_field::<E::ScalarField>(context));
+    verify::<E>(vk_path, proof_path, public_inputs)
+}
+
+// checks that an aggregated commitment opens to the revealed total
+//   blinding       : aggregated v, the commitment is recomputed
+//   blinding_proof : proof of knowledge of v from prover::prove_aggregated_total, v stays hidden
+pub fn verify_aggregated_total<E:Pairing>(
+    vk : &VerifyingKey<E>,
+    aggregated_commitment : &Commitment<E>,
+    total : &[E::ScalarField],
+    blinding : Option<E::ScalarField>,
+    blinding_proof : Option<&SchnorrProof<E::G1Affine>>
+) -> Result<bool> {
+    // aggregated commitment - g*total, h*v if the total is right
+    let total_commitment = Commitment::<E>::new(
+        calculate_pedersen_commitment::<E>(vk, total, E::ScalarField::from(0u64))?
+    );
+    let blinding_commitment = aggregated_commitment.sub(&total_commitment).point;
+
+    match (blinding, blinding_proof) {
+        (Some(v), _) => Ok(blinding_commitment == (vk.eta_gamma_inv_g1 * v).into()),
+        (None, Some(blinding_proof)) => Ok(blinding_proof.verify(
+            AGGREGATED_TOTAL_LABEL,
+            &[],
+            &[vk.eta_gamma_inv_g1],
+            &blinding_commitment
+        )),
+        (None, None) => Err(Error::Verification("either the blinding or a blinding proof is required".to_string())),
+    }
+}
+
+// verifies a proof from prover::prove_aggregate_range against the aggregated commitment
+// bit_width and limit must be the ones of the proof, limit < 2^bit_width
+// public_inputs : public inputs of the range circuit, in circuit order
+pub fn verify_aggregate_range<E:Pairing>(
+    vk : &VerifyingKey<E>,
+    proof : &Proof<E>,
+    aggregated_commitment : &Commitment<E>,
+    bit_width : usize,
+    limit : Option<E::ScalarField>,
+    public_inputs : &[E::ScalarField]
+) -> Result<bool> {
+    check_aggregate_limit::<E::ScalarField>(bit_width, limit)?;
+    if verify_proof(&prepare_verifying_key::<E>(vk), proof, public_inputs).is_err() {
+        return Ok(false);
+    }
+
+    let expected_d = match limit {
+        Some(limit) => Commitment::<E>::new(
+            calculate_pedersen_commitment::<E>(vk, &[limit], E::ScalarField::from(0u64))?
+        ).sub(aggregated_commitment),
+        None => *aggregated_commitment,
+    };
+    Ok(Commitment::from_proof(proof) == expected_d)
+}
+
+// proof.d and external_commitment commit to the same messages
+pub fn verify_commitment_equality<E:Pairing>(
+    vk : &VerifyingKey<E>,
+    proof : &Proof<E>,
+    external_bases : &PedersenBases<E::G1Affine>,
+    external_commitment : &E::G1Affine,
+    equality_proof : &EqualityProof<E::G1Affine>
+) -> bool {
+    equality_proof.verify(
+        COMMITMENT_EQUALITY_LABEL,
+        [&pedersen_bases::<E>(vk), external_bases],
+        [proof.d, *external_commitment]
+    )
+}
+
+// commitment : proof.d or an aggregated commitment, context as given to prover::prove_opening
+// the proof must open all vk.commit_witness_count messages and the blinding, partial openings are rejected
+pub fn verify_opening<E:Pairing>(
+    vk : &VerifyingKey<E>,
+    commitment : &Commitment<E>,
+    opening_proof : &SchnorrProof<E::G1Affine>,
+    context : &[u8]
+) -> bool {
+    if opening_proof.responses.len() != vk.commit_witness_count + 1 {
+        return false;
+    }
+    match pedersen_bases::<E>(vk).opening_bases(vk.commit_witness_count) {
+        Ok(bases) => opening_proof.verify(OPENING_LABEL, context, &bases, &commitment.point),
+        Err(_) => false,
+    }
+}
+
+// re-derives the link generators from the label and compares them with the ones in the link key
+// true if the key was made with setup::LinkGenerators::Hashed(label)
+pub fn verify_link_generators<E:Pairing>(
+    link_vk : &VerifyingKeyWithLink<E>,
+    label : &[u8]
+) -> bool {
+    verify_provided_link_generators::<E>(
+        link_vk,
+        &link_generators_from_label::<E>(label, link_vk.groth16_vk.commit_witness_count)
+    )
+}
+
+pub fn verify_link_generators_file<E:Pairing>(
+    link_vk_path : &str,
+    label : &[u8]
+) -> Result<bool> {
+    Ok(verify_link_generators::<E>(&read_link_verifying_key_from_file::<E>(link_vk_path)?, label))
+}
+
+// true if the link key uses exactly these link generators (setup::LinkGenerators::Provided)
+pub fn verify_provided_link_generators<E:Pairing>(
+    link_vk : &VerifyingKeyWithLink<E>,
+    link_generators : &LinkPublicGenerators<E>
+) -> bool {
+    link_generators_of(link_vk) == *link_generators
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Bn254, Fr, G1Affine};
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
+    };
+    use ark_r1cs_std::{alloc::AllocVar, eq::EqGadget, fields::fp::FpVar};
+    use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+    use crate::{
+        circuit::{test_util::{prove_values, range_pk}, CommittedCircuit, RangeCircuit},
+        context::context_input,
+        prover::{prove_aggregated_total, prove_circuit, prove_commitment_equality, prove_opening},
     };
-}
\ No newline at end of file
+
+    #[test]
+    fn aggregated_total_opens() {
+        let pk = range_pk(1);
+        let (proof_a, key_a) = prove_values(&pk, &[10], 1);
+        let (proof_b, key_b) = prove_values(&pk, &[20], 2);
+        let aggregated_commitment = Commitment::from_proof(&proof_a).add(&Commitment::from_proof(&proof_b));
+        let aggregated_opening_key = key_a.add(&key_b).unwrap();
+        let blinding_proof = prove_aggregated_total::<Bn254>(&pk.vk, &aggregated_opening_key).unwrap();
+        let total = [Fr::from(30u64)];
+
+        assert!(verify_aggregated_total::<Bn254>(&pk.vk, &aggregated_commitment, &total, Some(aggregated_opening_key.v), None).unwrap());
+        assert!(verify_aggregated_total::<Bn254>(&pk.vk, &aggregated_commitment, &total, None, Some(&blinding_proof)).unwrap());
+    }
+
+    #[test]
+    fn wrong_total_does_not_open() {
+        let pk = range_pk(1);
+        let (proof_a, key_a) = prove_values(&pk, &[10], 1);
+        let (proof_b, key_b) = prove_values(&pk, &[20], 2);
+        let aggregated_commitment = Commitment::from_proof(&proof_a).add(&Commitment::from_proof(&proof_b));
+        let aggregated_opening_key = key_a.add(&key_b).unwrap();
+        let blinding_proof = prove_aggregated_total::<Bn254>(&pk.vk, &aggregated_opening_key).unwrap();
+        let total = [Fr::from(31u64)];
+
+        assert!(!verify_aggregated_total::<Bn254>(&pk.vk, &aggregated_commitment, &total, Some(aggregated_opening_key.v), None).unwrap());
+        assert!(!verify_aggregated_total::<Bn254>(&pk.vk, &aggregated_commitment, &total, None, Some(&blinding_proof)).unwrap());
+        assert!(verify_aggregated_total::<Bn254>(&pk.vk, &aggregated_commitment, &total, None, None).is_err());
+        assert!(verify_aggregated_total::<Bn254>(&pk.vk, &aggregated_commitment, &[Fr::from(30u64), Fr::from(0u64)], None, Some(&blinding_proof)).is_err());
+    }
+
+    // the native range circuit in place of range_bits_8, same statement as prover::prove_aggregate_range
+    #[test]
+    fn aggregate_range_against_limit() {
+        let pk = range_pk(1);
+        let (proof_a, key_a) = prove_values(&pk, &[100], 1);
+        let (proof_b, key_b) = prove_values(&pk, &[120], 2);
+        let aggregated_commitment = Commitment::from_proof(&proof_a).add(&Commitment::from_proof(&proof_b));
+        let aggregated_opening_key = key_a.add(&key_b).unwrap();
+        let limit = Fr::from(250u64);
+
+        let circuit = RangeCircuit::new(8, &[limit - aggregated_opening_key.m[0]]).unwrap();
+        let (proof, _) = prove_circuit::<Bn254, _>(circuit, &pk, Some(-aggregated_opening_key.v), 3).unwrap();
+        assert!(verify_aggregate_range::<Bn254>(&pk.vk, &proof, &aggregated_commitment, 8, Some(limit), &[]).unwrap());
+        assert!(!verify_aggregate_range::<Bn254>(&pk.vk, &proof, &aggregated_commitment, 8, Some(Fr::from(251u64)), &[]).unwrap());
+        assert!(!verify_aggregate_range::<Bn254>(&pk.vk, &proof, &aggregated_commitment, 8, Some(limit), &[Fr::from(1u64)]).unwrap());
+
+        let circuit = RangeCircuit::new(8, &aggregated_opening_key.m).unwrap();
+        let (proof, _) = prove_circuit::<Bn254, _>(circuit, &pk, Some(aggregated_opening_key.v), 4).unwrap();
+        assert!(verify_aggregate_range::<Bn254>(&pk.vk, &proof, &aggregated_commitment, 8, None, &[]).unwrap());
+    }
+
+    #[test]
+    fn aggregate_range_limit_must_fit_the_circuit() {
+        let pk = range_pk(1);
+        let (proof, _) = prove_values(&pk, &[100], 1);
+        let aggregated_commitment = Commitment::from_proof(&proof);
+
+        assert!(matches!(
+            verify_aggregate_range::<Bn254>(&pk.vk, &proof, &aggregated_commitment, 8, Some(Fr::from(256u64)), &[]),
+            Err(Error::InvalidRange(_))
+        ));
+        assert!(matches!(
+            verify_aggregate_range::<Bn254>(&pk.vk, &proof, &aggregated_commitment, 254, None, &[]),
+            Err(Error::InvalidRange(_))
+        ));
+    }
+
+    fn external_bases(count : usize) -> PedersenBases<G1Affine> {
+        let mut rng = StdRng::seed_from_u64(7);
+        PedersenBases::new((0..count).map(|_| G1Affine::rand(&mut rng)).collect(), G1Affine::rand(&mut rng))
+    }
+
+    #[test]
+    fn commitment_equality_round_trip() {
+        let pk = range_pk(2);
+        let (proof, opening_key) = prove_values(&pk, &[5, 9], 1);
+        let bases = external_bases(2);
+        let external_blinding = Fr::from(1234u64);
+        let external_commitment = bases.commit(&opening_key.m, &external_blinding).unwrap();
+
+        let equality_proof = prove_commitment_equality::<Bn254>(&pk.vk, &opening_key, &bases, external_blinding).unwrap();
+        let equality_proof = EqualityProof::<G1Affine>::from_bytes(&equality_proof.to_bytes()).unwrap();
+        assert!(verify_commitment_equality::<Bn254>(&pk.vk, &proof, &bases, &external_commitment, &equality_proof));
+    }
+
+    #[test]
+    fn commitment_equality_rejects_other_messages() {
+        let pk = range_pk(2);
+        let (proof, opening_key) = prove_values(&pk, &[5, 9], 1);
+        let (other_proof, _) = prove_values(&pk, &[5, 10], 2);
+        let bases = external_bases(2);
+        let external_blinding = Fr::from(1234u64);
+        let external_commitment = bases.commit(&opening_key.m, &external_blinding).unwrap();
+        let other_commitment = bases.commit(&[Fr::from(5u64), Fr::from(10u64)], &external_blinding).unwrap();
+        let equality_proof = prove_commitment_equality::<Bn254>(&pk.vk, &opening_key, &bases, external_blinding).unwrap();
+
+        assert!(!verify_commitment_equality::<Bn254>(&pk.vk, &other_proof, &bases, &external_commitment, &equality_proof));
+        assert!(!verify_commitment_equality::<Bn254>(&pk.vk, &proof, &bases, &other_commitment, &equality_proof));
+        assert!(!verify_commitment_equality::<Bn254>(&pk.vk, &proof, &external_bases(1), &external_commitment, &equality_proof));
+
+        let mut tampered = equality_proof.clone();
+        tampered.m_responses[0] += Fr::from(1u64);
+        assert!(!verify_commitment_equality::<Bn254>(&pk.vk, &proof, &bases, &external_commitment, &tampered));
+        let mut tampered = equality_proof;
+        tampered.v_responses[1] += Fr::from(1u64);
+        assert!(!verify_commitment_equality::<Bn254>(&pk.vk, &proof, &bases, &external_commitment, &tampered));
+    }
+
+    #[test]
+    fn opening_proof_for_proof_and_aggregate() {
+        let pk = range_pk(1);
+        let (proof_a, key_a) = prove_values(&pk, &[10], 1);
+        let (proof_b, key_b) = prove_values(&pk, &[20], 2);
+        let aggregated_commitment = Commitment::from_proof(&proof_a).add(&Commitment::from_proof(&proof_b));
+
+        let opening_proof = prove_opening::<Bn254>(&pk.vk, &key_a, b"nonce").unwrap();
+        assert!(verify_opening::<Bn254>(&pk.vk, &Commitment::from_proof(&proof_a), &opening_proof, b"nonce"));
+        assert!(!verify_opening::<Bn254>(&pk.vk, &Commitment::from_proof(&proof_b), &opening_proof, b"nonce"));
+        assert!(!verify_opening::<Bn254>(&pk.vk, &Commitment::from_proof(&proof_a), &opening_proof, b"replayed"));
+
+        let aggregated_proof = prove_opening::<Bn254>(&pk.vk, &key_a.add(&key_b).unwrap(), b"nonce").unwrap();
+        assert!(verify_opening::<Bn254>(&pk.vk, &aggregated_commitment, &aggregated_proof, b"nonce"));
+
+        let mut tampered = aggregated_proof;
+        tampered.responses[0] += Fr::from(1u64);
+        assert!(!verify_opening::<Bn254>(&pk.vk, &aggregated_commitment, &tampered, b"nonce"));
+    }
+
+    // RangeCircuit with the public context input of circom/range/range_context_64.circom
+    #[derive(Clone)]
+    struct ContextCircuit {
+        range : RangeCircuit<Fr>,
+        context : Option<Fr>,
+    }
+
+    impl ConstraintSynthesizer<Fr> for ContextCircuit {
+        fn generate_constraints(self, cs : ConstraintSystemRef<Fr>) -> std::result::Result<(), SynthesisError> {
+            let context = FpVar::new_input(cs.clone(), || self.context.ok_or(SynthesisError::AssignmentMissing))?;
+            self.range.generate_constraints(cs.clone())?;
+            let context_squared = FpVar::new_witness(cs, || {
+                self.context.map(|context| context * context).ok_or(SynthesisError::AssignmentMissing)
+            })?;
+            (&context * &context).enforce_equal(&context_squared)
+        }
+    }
+
+    impl CommittedCircuit<Fr> for ContextCircuit {
+        fn commit_witness_count(&self) -> usize {
+            self.range.commit_witness_count()
+        }
+
+        fn committed_witnesses(&self) -> Option<Vec<Fr>> {
+            self.range.committed_witnesses()
+        }
+    }
+
+    #[test]
+    fn context_proof_verifies_only_for_its_context() {
+        let dir = std::env::temp_dir().join(format!("verifier_{}_context", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (vk_path, proof_path) = (dir.join("vk.bin"), dir.join("proof.bin"));
+        let (vk_path, proof_path) = (vk_path.to_str().unwrap(), proof_path.to_str().unwrap());
+
+        let setup_circuit = ContextCircuit { range : RangeCircuit::for_setup(8, 1).unwrap(), context : None };
+        let pk = crate::setup::setup_circuit::<Bn254, _>(setup_circuit, 0).unwrap();
+        std::fs::write(vk_path, crate::keys::compressed_proving_key_bytes::<Bn254>(&pk).unwrap().1).unwrap();
+
+        let (_, context) = context_input::<Fr>(b"session a");
+        let circuit = ContextCircuit { range : RangeCircuit::new(8, &[Fr::from(42u64)]).unwrap(), context : Some(context[0]) };
+        let (proof, _) = prove_circuit::<Bn254, _>(circuit, &pk, None, 1).unwrap();
+        std::fs::write(proof_path, crate::prover::proof_to_bytes(&proof)).unwrap();
+
+        assert!(verify_with_context::<Bn254>(vk_path, proof_path, vec![], b"session a").unwrap());
+        assert!(!verify_with_context::<Bn254>(vk_path, proof_path, vec![], b"session b").unwrap());
+        assert!(verify::<Bn254>(vk_path, proof_path, context).unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // a proof over the first message only must not pass for a key of two messages
+    #[test]
+    fn opening_proof_must_open_every_message() {
+        use ark_ec::CurveGroup;
+
+        let pk = range_pk(2);
+        let (proof, opening_key) = prove_values(&pk, &[10, 20], 1);
+        let commitment = Commitment::from_proof(&proof);
+        let opening_proof = prove_opening::<Bn254>(&pk.vk, &opening_key, b"nonce").unwrap();
+        assert!(verify_opening::<Bn254>(&pk.vk, &commitment, &opening_proof, b"nonce"));
+
+        let mut truncated = opening_proof.clone();
+        truncated.responses.remove(1);
+        assert!(!verify_opening::<Bn254>(&pk.vk, &commitment, &truncated, b"nonce"));
+
+        // g_0*m_0 + h*v is the statement of the truncated list, it verifies under the first base and h
+        let bases = pedersen_bases::<Bn254>(&pk.vk);
+        let partial = bases.g[0] * opening_key.m[0] + bases.h * opening_key.v;
+        let partial = Commitment::<Bn254>::new(partial.into_affine());
+        let partial_proof = SchnorrProof::prove(
+            OPENING_LABEL, b"nonce", &[bases.g[0], bases.h], &partial.point, &[opening_key.m[0], opening_key.v]
+        ).unwrap();
+        assert!(!verify_opening::<Bn254>(&pk.vk, &partial, &partial_proof, b"nonce"));
+
+        let mut extended = opening_proof;
+        extended.responses.push(Fr::from(1u64));
+        assert!(!verify_opening::<Bn254>(&pk.vk, &commitment, &extended, b"nonce"));
+    }
+}
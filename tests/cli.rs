@@ -0,0 +1,129 @@
+// runs the legogroth16 binary, cargo test --no-default-features --features cli
+#![cfg(feature = "cli")]
+
+use serde_json::Value;
+use std::{
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+fn temp_dir(name : &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("legogroth16_cli_{}_{}", std::process::id(), name));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn legogroth16(args : &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_legogroth16")).args(args).output().unwrap()
+}
+
+fn stdout_json(output : &Output) -> Value {
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn native_range_commands_and_exit_codes() {
+    let dir = temp_dir("native");
+    let file = |name : &str| dir.join(name).to_string_lossy().to_string();
+    let (pk, vk, proof) = (file("pk.bin"), file("vk.bin"), file("proof.bin"));
+
+    let output = legogroth16(&["setup-range", "--bits", "8", "--pk", &pk, "--vk", &vk, "--seed", "7"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout_json(&output)["seed"], 7);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("warning"));
+
+    let output = legogroth16(&["prove-range", "--pk", &pk, "--proof", &proof, "--bits", "8", "--value", "42"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout_json(&output)["opening_key"], file("proof_opening_key.json"));
+
+    let output = legogroth16(&["verify", "--vk", &vk, "--proof", &proof]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout_json(&output)["verified"], true);
+
+    let output = legogroth16(&["open", "--vk", &vk, "--opening-key", &file("proof_opening_key.json"), "--proof", &proof]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout_json(&output)["opens"], true);
+
+    let output = legogroth16(&["inspect", "vk", &vk]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout_json(&output)["commit_witness_count"], 1);
+
+    // a random setup does not echo a seed, its keys do not verify the proof
+    let (other_pk, other_vk) = (file("other_pk.bin"), file("other_vk.bin"));
+    let output = legogroth16(&["setup-range", "--bits", "8", "--pk", &other_pk, "--vk", &other_vk]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout_json(&output).get("seed").is_none());
+    assert!(output.stderr.is_empty());
+
+    let output = legogroth16(&["verify", "--vk", &other_vk, "--proof", &proof]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout_json(&output)["verified"], false);
+
+    let output = legogroth16(&["open", "--vk", &other_vk, "--opening-key", &file("proof_opening_key.json"), "--proof", &proof]);
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = legogroth16(&["verify", "--vk", &vk, "--proof", &file("missing.bin")]);
+    assert_eq!(output.status.code(), Some(2));
+    let error : Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert!(error["error"].is_string());
+
+    let output = legogroth16(&["prove-range", "--pk", &pk, "--proof", &proof, "--bits", "8", "--value", "256"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn circom_commands() {
+    let dir = temp_dir("circom");
+    let file = |name : &str| dir.join(name).to_string_lossy().to_string();
+    let (r1cs, wasm) = ("circom/bn128/range_bits_8.r1cs", "circom/bn128/range_bits_8.wasm");
+    let (pk, vk) = (file("pk.bin"), file("vk.bin"));
+    let (link_pk, link_vk) = (file("link_pk.bin"), file("link_vk.bin"));
+
+    let output = legogroth16(&[
+        "setup", "--r1cs", r1cs, "--pk", &pk, "--vk", &vk,
+        "--link-label", "cli-test", "--link-pk", &link_pk, "--link-vk", &link_vk,
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout_json(&output).get("seed").is_none());
+
+    assert_eq!(legogroth16(&["check-link", "--link-vk", &link_vk, "--label", "cli-test"]).status.code(), Some(0));
+    assert_eq!(legogroth16(&["check-link", "--link-vk", &link_vk, "--label", "other"]).status.code(), Some(1));
+
+    for (proof, value) in [("a.bin", "10"), ("b.bin", "20")] {
+        let input = format!("value={}", value);
+        let output = legogroth16(&["prove", "--r1cs", r1cs, "--wasm", wasm, "--pk", &pk, "--proof", &file(proof), "--input", &input]);
+        assert_eq!(output.status.code(), Some(0));
+        assert_eq!(legogroth16(&["verify", "--vk", &vk, "--proof", &file(proof)]).status.code(), Some(0));
+    }
+
+    let output = legogroth16(&[
+        "aggregate", "--proof", &file("a.bin"), "--proof", &file("b.bin"), "--out", &file("aggregated_commitment.bin"),
+        "--opening-key", &file("a_opening_key.json"), "--opening-key", &file("b_opening_key.json"),
+        "--opening-key-out", &file("aggregated_opening_key.json"),
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout_json(&output)["proofs"], 2);
+
+    let output = legogroth16(&[
+        "update", "--r1cs", r1cs, "--wasm", wasm, "--pk", &pk, "--proof", &file("a.bin"),
+        "--aggregated-commitment", &file("aggregated_commitment.bin"),
+        "--aggregated-opening-key", &file("aggregated_opening_key.json"), "--value", "30",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = legogroth16(&[
+        "open", "--vk", &vk, "--opening-key", &file("aggregated_opening_key.json"),
+        "--commitment", &file("aggregated_commitment.bin"),
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    let output = legogroth16(&["inspect", "opening-key", &file("aggregated_opening_key.json")]);
+    assert_eq!(stdout_json(&output)["m"][0], format!("{:0>64}", "32"));
+
+    // the witness calculator rejects values of 9 bits
+    let output = legogroth16(&["prove", "--r1cs", r1cs, "--wasm", wasm, "--pk", &pk, "--proof", &file("c.bin"), "--input", "value=256"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}